@@ -14,14 +14,27 @@
 **/
 
 use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
 use opstring::OpString;
 
 
 /***** CUSTOM TYPES *****/
 /// Defines a shortcut for the Positional's HashMap in the ArgsDict.
 type PositionalHashMap = HashMap<String, (usize, String)>;
+/// Defines a shortcut for the variadic Positional's HashMap in the ArgsDict.
+type MultiPositionalHashMap = HashMap<String, (usize, Vec<String>)>;
 /// Defines a shortcut for the Option's HashMap in the ArgsDict.
 type OptionHashMap = HashMap<String, (String, String, Vec<String>)>;
+/// Defines a shortcut for the negatable flag's HashMap in the ArgsDict.
+type FlagHashMap = HashMap<String, (String, bool)>;
+/// Defines a shortcut for the counted option's HashMap in the ArgsDict.
+type CountHashMap = HashMap<String, usize>;
+/// Defines a shortcut for the HashMap with the sources of options that were not given on the command line in the ArgsDict.
+type SourceHashMap = HashMap<String, Source>;
+/// Defines a shortcut for the HashMap with how the options take values in the ArgsDict. Each option is mapped to its maximum number of values, whether that maximum holds per occurrence, and its value terminator.
+type LimitHashMap = HashMap<String, (usize, bool, std::option::Option<String>)>;
 
 
 
@@ -37,12 +50,25 @@ pub const HELP_LONGNAME: &str = "help";
 /// The description used for the help argument.
 pub const HELP_DESCRIPTION: &str = "Shows this list of arguments, then quits.";
 
+/// The indent width used for the help string if none is set.
+pub const DEFAULT_INDENT_WIDTH: usize = 20;
+/// The line width used for the help string if none is set and the terminal width cannot be found.
+pub const DEFAULT_LINE_WIDTH: usize = 80;
+/// The distance between tab stops in descriptions if none is set.
+const DEFAULT_TAB_WIDTH: usize = 4;
+/// The graphemes that separate words in descriptions if none are set.
+const DEFAULT_WORD_SEPARATORS: &[&str] = &[" ", "\n", "\t", "\r"];
+/// The largest indent width that is computed automatically if `set_auto_indent()` is used.
+const MAX_AUTO_INDENT_WIDTH: usize = 40;
+
 
 
 
 
 /***** MACROS *****/
 /// Shortcut to getting the arguments from the environment
+/// 
+/// Expands to an expression with the arguments as a `Vec<String>`, e.g., `let args = get_args_from_env!();`.
 #[macro_export]
 macro_rules! get_args_from_env {
     () => {
@@ -249,481 +275,5333 @@ mod tests {
         assert_eq!(dict.has_errors(), true);
         assert_eq!(dict.get_errors().len(), 3);
     }
-}
 
+    #[test]
+    fn error_kinds() {
+        // Create a parser with a few options
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.");
+        parser.add_opt("opt2", "", "opt2", 2, 3, "", "Another test option.");
 
+        // Parse a few wrong things
+        let args = vec!(String::from("./test_exec"), String::from("-otest"), String::from("--opt2=test"), String::from("--opt3"), String::from("--opt2"), String::from("test"));
+        let dict = parser.parse(&args);
 
+        // Check the errors are what we expect
+        assert_eq!(dict.get_errors(), &vec!(
+            ParseError::UnexpectedValue{ option: String::from("-o"), index: 1, value: String::from("test") },
+            ParseError::InlineValueUnsupported{ option: String::from("--opt2"), index: 2, max: 3 },
+            ParseError::UnknownOption{ option: String::from("--opt3"), index: 3, help_hint: false, suggestion: Some(String::from("--opt1")) },
+            ParseError::MissingValues{ option: String::from("--opt2"), index: Some(4), expected: 2, got: 1 },
+        ));
+        assert_eq!(dict.get_errors()[3].to_string(), "Not enough values for '--opt2' (argument 4): expected at least 2, got 1.");
+    }
 
+    #[test]
+    fn iterators() {
+        // Create a parser with several positionals and options
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_pos("pos2", "pos2", "Another test positional.");
+        parser.add_pos("pos3", "pos3", "Yet another test positional.");
+        parser.add_opt("opt1", "o", "opt1", 0, 1, "", "A test option.");
+        parser.add_opt("opt2", "", "opt2", 0, 0, "", "Another test option.");
 
-/***** HELPER STRUCTS *****/
-/// Intermediate representation for a Positional.
-struct Positional {
-    /// The uid for this positional.
-    uid         : String,
-    /// The index of this positional.
-    index       : usize,
-    /// The human-readable name for this positional. Used in the usage/help string.
-    name        : String,
-    /// The description for this positional.
-    description : String,
-}
+        // Parse some of them
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-o"), String::from("test2"), String::from("test3"), String::from("test4"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
-/// Intermediate representation for an Option.
-struct Option {
-    /// The uid for this option.
-    uid               : String,
-    /// The shortname for this option. Will be the empty char (`\0`) if unused.
-    shortname         : String,
-    /// The longname for this option.
-    longname          : String,
-    /// The minimum number of values for this option.
-    min_n_values      : usize,
-    /// The maximum number of values for this option.
-    max_n_values      : usize,
-    /// The description of the parameters for this option.
-    param_description : String,
-    /// The description for this option.
-    description       : String,
-}
+        // The positionals should be in order
+        let positionals: Vec<(&str, &str)> = dict.iter_pos().collect();
+        assert_eq!(positionals, vec!(("pos1", "test1"), ("pos2", "test3"), ("pos3", "test4")));
+        // The only option should be opt1
+        let options: Vec<(&str, &Vec<String>)> = dict.iter_opts().collect();
+        assert_eq!(options, vec!(("opt1", &vec!(String::from("test2")))));
+    }
 
+    #[test]
+    fn chained_parser() {
+        // Create a parser by chaining registrations
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 0, 1, "", "A test option.")
+              .add_double_dash()
+              .add_help();
 
+        // Parse a value for each
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("test1"), String::from("--"), String::from("-test2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap()[0], "test1");
+        assert_eq!(dict.get_pos("pos1").unwrap(), "-test2");
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [options] <pos1>");
+    }
 
+    #[test]
+    fn mutex_parser() {
+        // Create a parser with a group of mutually exclusive options
+        let mut parser = ArgParser::new();
+        parser.add_opt("json", "", "json", 0, 0, "", "Output as JSON.")
+              .add_opt("yaml", "", "yaml", 0, 0, "", "Output as YAML.")
+              .add_opt("toml", "", "toml", 0, 0, "", "Output as TOML.")
+              .add_mutex_group(vec!(String::from("json"), String::from("yaml"), String::from("toml")));
 
+        // Parse only one of them
+        let mut args = vec!(String::from("./test_exec"), String::from("--yaml"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
-/***** WORDITERATOR HELPER CLASS *****/
-/// Helper iterator over a string, that returns word-by-word instead of char-by-char.
-/// 
-/// Uses the graphene method to have intuitive characters.
-/// 
-/// **Template parameters**
-///  * `'a`: The lifetime parameter for the WorldIterator, which should be itself.
-struct WordIterator<'a> {
-    /// The string we iterate over
-    s    : OpString<'a>,
-    /// The current position in the string
-    i    : usize,
-}
+        // Parse two of them
+        args = vec!(String::from("./test_exec"), String::from("--yaml"), String::from("--json"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Options '--json' and '--yaml' are mutually exclusive.");
 
-impl<'a> WordIterator<'a> {
-    /// Constructor for the WordIterator class
-    fn new(s: &'a str) -> WordIterator {
-        // Return the new WordIterator
-        return WordIterator {
-            s    : OpString::new(s),
-            i    : 0
-        };
+        // Parse all of them
+        args = vec!(String::from("./test_exec"), String::from("--toml"), String::from("--yaml"), String::from("--json"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Options '--json', '--yaml' and '--toml' are mutually exclusive.");
     }
-}
-
-impl<'a> Iterator for WordIterator<'a> {
-    /// The type of each item returned by the iterator
-    type Item = (&'a str, &'a str);
 
-    /// Gets the next word/separator pair in the internal string.  
-    /// A separator is what splits words, and can either be any whitespace (space, newline (`\n`), carriage return (`\r`) or tab (`\t`)) or a null-character (`\0`) in case of end-of-string.
-    /// 
-    /// **Returns**  
-    /// An Option with, if we didn't reach the end yet, a tuple bearing the word (possibly empty in case of two consecutive separators) and the separator following after it.
-    fn next(&mut self) -> std::option::Option<Self::Item> {
-        // Continue with iterating where we were
-        let start_i = self.i;
-        loop {
-            // Get the next char
-            let c: &str;
-            if self.i < self.s.len() { c = self.s[self.i]; }
-            else { c = "\0"; }
+    #[test]
+    fn requires_parser() {
+        // Create a parser where one option requires another
+        let mut parser = ArgParser::new();
+        parser.add_opt("format", "f", "format", 1, 1, "<format>", "The output format.")
+              .add_opt("output_file", "o", "output-file", 1, 1, "<path>", "The output file.")
+              .add_requires("output_file", "format");
 
-            // See if it's a separator
-            if c.eq(" ") || c.eq("\n") || c.eq("\t") || c.eq("\r") || c.eq("\0") {
-                // It is; return the result + the separator
-                let start_j = self.s.translate_opstr(start_i);
-                let end_j   = self.s.translate_opstr(self.i);
-                self.i += c.len();
-                return Some((&self.s.parent()[start_j..end_j], c));
-            }
+        // Parse neither
+        let mut args = vec!(String::from("./test_exec"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
-            // Otherwise, move the internal i
-            self.i += c.len();
-        }
-    }
-}
+        // Parse both
+        args = vec!(String::from("./test_exec"), String::from("--output-file"), String::from("test.txt"), String::from("--format"), String::from("txt"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
+        // Parse only the required one
+        args = vec!(String::from("./test_exec"), String::from("--format"), String::from("txt"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
+        // Parse only the one with the requirement
+        args = vec!(String::from("./test_exec"), String::from("--output-file"), String::from("test.txt"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Option '--output-file' requires '--format' to also be specified.");
+    }
 
+    #[test]
+    fn delimited_opt_parser() {
+        // Create a parser with an option with a delimiter
+        let mut parser = ArgParser::new();
+        parser.add_opt("tags", "t", "tags", 1, 3, "<tag>[,<tag>...]", "The tags to use.")
+              .set_value_delimiter("tags", ',', false);
 
+        // Parse a list of tags
+        let mut args = vec!(String::from("./test_exec"), String::from("--tags"), String::from("a,b,c"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("tags").unwrap(), &vec!(String::from("a"), String::from("b"), String::from("c")));
 
-/***** ARGPARSER CLASS *****/
-/// Defines a single instance for arguments.
-pub struct ArgParser {
-    /// Stores the defined positionals in the parser.
-    positionals     : Vec<Positional>,
-    /// Stores the defined options in the parser.
-    options         : Vec<Option>,
+        // Empty values are dropped
+        args = vec!(String::from("./test_exec"), String::from("--tags"), String::from("a,,b,"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_opt("tags").unwrap(), &vec!(String::from("a"), String::from("b")));
 
-    /// Determines whether or not the double-dash argument is used
-    use_double_dash : bool,
-    /// Determines whether or not the help is given
-    use_help        : bool,
-}
+        // The limits apply to the split values
+        args = vec!(String::from("./test_exec"), String::from("--tags"), String::from("a,b"), String::from("c,d"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
 
-/// Defines the ArgParser's methods
-impl ArgParser {
-    /// Constructor for the ArgParser, which is public.
-    pub fn new() -> ArgParser {
-        ArgParser {
-            positionals     : Vec::new(),
-            options         : Vec::new(),
-            use_double_dash : false,
-            use_help        : false
-        }
+        // Unless they are kept
+        parser.set_value_delimiter("tags", ',', true);
+        args = vec!(String::from("./test_exec"), String::from("--tags"), String::from("a,,b"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_opt("tags").unwrap(), &vec!(String::from("a"), String::new(), String::from("b")));
     }
 
-    
+    #[test]
+    fn help_parser() {
+        // Create a parser with help and another option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 1, 1, "", "A test option.")
+              .add_help();
 
-    /// Helper function that parses at most max_n values from the given list of arguments.
-    /// 
-    /// **Arguments**
-    ///  * `args`: The list of arguments to parse from.
-    ///  * `i`: Reference to the current position within args. Will be increment as we parse, and is left at the last-parsed argument.
-    ///  * `max_n`: The maximum number of arguments to parse.
-    ///  * `parse_opts`: Whether or not options are still allowed to be parsed. Might be adapted if we have use_double_dash set and we encounter it.
-    ///  * `use_double_dash`: Whether or not the function should look out for the double dash, option-disabling arg.
-    /// **Returns**  
-    /// The popped arguments, of which there will be at most max_n.
-    fn parse_values(args: &Vec<String>, i: &mut usize, max_n: usize, parse_opts: &mut bool, use_double_dash: bool) -> Vec<String> {
-        // Increment i to skip the option itself
-        *i += 1;
-        let start_i = *i;
+        // Parse help with a wrong option
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("--opt1"), String::from("--help"));
+        let dict = parser.parse(&args);
 
-        // Try to pop
-        let mut result: Vec<String> = Vec::new();
-        while *i < args.len() && *i - start_i < max_n {
-            // Get the argument
-            let arg = &args[*i];
-            let sarg = OpString::new(arg);
-            if sarg.len() == 0 { continue; }
+        // Only the help should be there, which is up to us to print
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_help(), true);
+        assert_eq!(dict.has_pos("pos1"), false);
+        assert_eq!(dict.has_opt("opt1"), false);
+
+        // Nothing should be written by default, only if told to
+        let mut buffer: Vec<u8> = Vec::new();
+        parser.write_auto_help(&mut buffer, "./test_exec", None).unwrap();
+        assert_eq!(buffer.len(), 0);
+        parser.set_auto_help(true);
+        parser.write_auto_help(&mut buffer, "./test_exec", None).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), parser.get_help_for_columns("./test_exec", None));
+    }
 
-            // If it's an option, stop
-            if *parse_opts && sarg[0].eq("-") {
-                // Make sure its not the other one
-                if use_double_dash && sarg.len() == 2 && sarg[1].eq("-") {
-                    *parse_opts = false;
-                    *i += 1;
-                    continue;
-                }
-                break;
-            }
+    #[test]
+    fn try_parse() {
+        // Create a parser with one positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
 
-            // Otherwise, add to the result
-            result.push(arg.clone());
+        // Parse a valid string, plus a superfluous positional
+        let mut args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"));
+        let dict = parser.try_parse(&args).unwrap();
+        // The positional should be there, and the warning should be kept
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_warnings().len(), 1);
+
+        // Now parse a few unknown options
+        args = vec!(String::from("./test_exec"), String::from("--test1"), String::from("--test2"));
+        let errors = parser.try_parse(&args).err().unwrap();
+        // Both errors should be returned in order
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], ParseError::UnknownOption{ option: String::from("--test1"), index: 1, help_hint: false, suggestion: None });
+        assert_eq!(errors[1].to_string(), "Unknown option '--test2' (argument 2)");
+    }
 
-            // Increment i
-            *i += 1;
-        }
+    #[test]
+    fn typed_getters() {
+        // Create a parser with a positional and two options
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<int>", "A test option.");
+        parser.add_opt("opt2", "", "opt2", 1, 3, "<float>...", "Another test option.");
 
-        // i is now at the first unparseable thing; fix this for the main increment
-        *i -= 1;
+        // Parse some values
+        let args = vec!(String::from("./test_exec"), String::from("abc"), String::from("-o"), String::from("42"), String::from("--opt2"), String::from("1.5"), String::from("2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
 
-        // Return the result struct
-        return result;
+        // Check the typed values
+        assert_eq!(dict.get_opt_as::<i32>("opt1").unwrap().unwrap(), 42);
+        assert_eq!(dict.get_opt_as::<f64>("opt2").unwrap().unwrap(), 1.5);
+        let values: Vec<f64> = dict.get_opt_all_as::<f64>("opt2").unwrap().into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(values, vec!(1.5, 2.0));
+        // The positional cannot be parsed as a number
+        assert_eq!(dict.get_pos_as::<u8>("pos1").unwrap().is_err(), true);
+        assert_eq!(dict.get_pos_as::<String>("pos1").unwrap().unwrap(), "abc");
+        // Unknown uids result in None
+        assert_eq!(dict.get_opt_as::<i32>("opt3").is_none(), true);
+        assert_eq!(dict.get_pos_as::<i32>("pos2").is_none(), true);
     }
 
-    /// Generates a string of n spaces.
-    /// 
-    /// **Arguments**
-    ///  * `N`: The number of spaces to generate.
-    /// 
-    /// **Returns**  
-    /// A string with N spaces.
-    fn generate_spaces(n: usize) -> String {
-        // Create a large enough string
-        let mut result: String = String::new();
-        result.reserve(n);
+    #[test]
+    fn env_opt_parser() {
+        // Create a parser with options that fall back to the environment
+        let mut parser = ArgParser::new();
+        parser.add_opt_env("opt1", "o", "opt1", 0, 3, "", "A test option.", "PARSE_ARGS_TEST_OPT1");
+        parser.add_opt_env("opt2", "", "opt2", 1, 1, "", "Another test option.", "PARSE_ARGS_TEST_OPT2");
+        parser.add_opt_env("opt3", "", "opt3", 0, 1, "", "Yet another test option.", "PARSE_ARGS_TEST_OPT3");
+        parser.add_opt_env("flag4", "", "flag4", 0, 0, "", "A test flag.", "PARSE_ARGS_TEST_FLAG4");
+        std::env::set_var("PARSE_ARGS_TEST_OPT1", "1 2  3");
+        std::env::set_var("PARSE_ARGS_TEST_OPT2", "hello world");
+        std::env::remove_var("PARSE_ARGS_TEST_OPT3");
+
+        // Parse without any options
+        let mut args = vec!(String::from("./test_exec"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        // The values should be taken from the environment
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("1"), String::from("2"), String::from("3")));
+        assert_eq!(dict.get_opt("opt2").unwrap(), &vec!(String::from("hello world")));
+        assert_eq!(dict.has_opt("opt3"), false);
 
-        // Write the spaces
-        for _ in 0..n {
-            result.push(' ');
-        }
+        // The command line takes priority
+        args = vec!(String::from("./test_exec"), String::from("--opt2"), String::from("test"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt2").unwrap(), &vec!(String::from("test")));
 
-        // Done
-        return result;
-    }
+        // Values from the environment are validated too
+        std::env::set_var("PARSE_ARGS_TEST_OPT1", "1 2 3 4");
+        args = vec!(String::from("./test_exec"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        std::env::set_var("PARSE_ARGS_TEST_OPT1", "1 2 3");
 
-    /// Helper function that adds the given description linewrapped to the given string.
-    /// 
-    /// **Arguments**
-    ///  * `result`: The string to append the result to.
-    ///  * `x`: The current column position on the line. Will be updated as we write.
-    ///  * `description`: The description to write.
-    ///  * `indent_width`: The width before each new line.
-    ///  * `line_width`: The line width to break on.
-    fn print_description(result: &mut String, x: &mut usize, description: &str, indent_width: usize, line_width: usize) {
-        // Make sure indent_width and line_width aren't conflicting
-        if indent_width >= line_width {
-            panic!("Cannot have an indent width larger than or equal to a line width: {} >= {}", indent_width, line_width);
+        // Flags are given by any value, except for those that mean false
+        for (value, given) in [("1", true), ("yes", true), ("0", false), ("False", false), ("no", false), ("", false)] {
+            std::env::set_var("PARSE_ARGS_TEST_FLAG4", value);
+            assert_eq!(parser.parse(&args).has_opt("flag4"), given);
         }
+        std::env::remove_var("PARSE_ARGS_TEST_FLAG4");
+        assert_eq!(parser.parse(&args).has_opt("flag4"), false);
+    }
 
-        // Generate the indent spaces
-        let indent = ArgParser::generate_spaces(indent_width);
+    #[test]
+    fn flag_parser() {
+        // Create a parser with one negatable flag
+        let mut parser = ArgParser::new();
+        parser.add_flag("flag1", "f", "flag1", "A test flag.");
 
-        // Go through the description word-by-word
-        for (word, separator) in WordIterator::new(description) {
-            // Wrap the word in an OpString
-            let oword = OpString::new(word);
+        // Parse without the flag
+        let mut args = vec!(String::from("./test_exec"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_flag("flag1"), None);
 
-            // Only do stuff if the parsed word has at least one char
-            if word.len() > 0 {
-                // See if we need to go to the next line
-                if *x != indent_width && *x + word.len() + 1 >= line_width {
-                    // Add a new line plus the indent
-                    result.reserve(1 + indent_width);
-                    result.push('\n');
-                    result.push_str(indent.as_str());
+        // Parse with the positive form
+        args = vec!(String::from("./test_exec"), String::from("-f"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_flag("flag1"), Some(true));
+        assert_eq!(dict.has_opt("flag1"), true);
 
-                    // Reset the x
-                    *x = indent_width;
-                }
+        // Parse with the negative form
+        args = vec!(String::from("./test_exec"), String::from("--no-flag1"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_flag("flag1"), Some(false));
+        assert_eq!(dict.has_opt("flag1"), false);
 
-                // Now loop through the word to write it, possibly linewrapped
-                result.reserve(word.len() + word.len() / (line_width - indent_width));
-                for c in oword.chars() {
-                    // Split if needed
-                    if *x >= line_width {
-                        // Add a new line plus the indent
-                        result.reserve(1 + indent_width);
-                        result.push('\n');
-                        result.push_str(indent.as_str());
+        // The last one should win
+        args = vec!(String::from("./test_exec"), String::from("--no-flag1"), String::from("--flag1"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_flag("flag1"), Some(true));
+        args = vec!(String::from("./test_exec"), String::from("--flag1"), String::from("--no-flag1"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_flag("flag1"), Some(false));
+    }
 
-                        // Reset the x
-                        *x = indent_width;
-                    }
+    #[test]
+    #[should_panic]
+    fn flag_conflict() {
+        // The negated longname should conflict with existing options
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "", "no-flag1", 0, 0, "", "A test option.");
+        parser.add_flag("flag1", "f", "flag1", "A test flag.");
+    }
 
-                    // Write the letter
-                    result.push_str(c);
-                    *x += 1;
+    #[test]
+    fn required_opt_parser() {
+        // Create a parser with one required option and help
+        let mut parser = ArgParser::new();
+        parser.add_opt_required("opt1", "o", "opt1", 1, 1, "<value>", "A test option.", true);
+        parser.add_opt("opt2", "", "opt2", 0, 0, "", "Another test option.");
+        parser.add_help();
+
+        // Parse without the required option
+        let mut args = vec!(String::from("./test_exec"), String::from("--opt2"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0], ParseError::MissingRequired{ option: String::from("--opt1") });
+        assert_eq!(dict.get_errors()[0].to_string(), "Missing required option '--opt1'.");
+
+        // Parse with the required option
+        args = vec!(String::from("./test_exec"), String::from("-o"), String::from("test"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap()[0], "test");
+
+        // Help should still short-circuit
+        args = vec!(String::from("./test_exec"), String::from("--help"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_help(), true);
+    }
+
+    #[test]
+    fn choices_opt_parser() {
+        // Create a parser with one restricted option
+        let mut parser = ArgParser::new();
+        parser.add_opt_choices("mode", "m", "mode", 1, 1, "<mode>", "The mode to run in.", vec!(String::from("debug"), String::from("release"), String::from("test")));
+
+        // Parse a valid value
+        let mut args = vec!(String::from("./test_exec"), String::from("--mode"), String::from("release"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("mode").unwrap()[0], "release");
+
+        // Parse an invalid value
+        args = vec!(String::from("./test_exec"), String::from("--mode"), String::from("Release"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Invalid value 'Release' for '--mode' (argument 1): expected one of [debug, release, test].");
+
+        // The help should list the choices
+        assert_eq!(parser.get_help("./test_exec", 20, 80).contains("Choices: debug, release, test"), true);
+    }
+
+    #[test]
+    fn subcommand_parser() {
+        // Create two subcommand parsers
+        let mut add_parser = ArgParser::new();
+        add_parser.add_pos("file", "file", "The file to add.");
+        add_parser.add_opt("force", "f", "force", 0, 0, "", "Forces the add.");
+        let mut rm_parser = ArgParser::new();
+        rm_parser.add_pos("file", "file", "The file to remove.");
+
+        // Create the main parser
+        let mut parser = ArgParser::new();
+        parser.add_opt("verbose", "v", "verbose", 0, 0, "", "Be verbose.");
+        parser.add_subcommand("add", add_parser);
+        parser.add_subcommand("rm", rm_parser);
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [options] <add|rm> ...");
+
+        // Parse a subcommand
+        let mut args = vec!(String::from("./test_exec"), String::from("-v"), String::from("add"), String::from("-f"), String::from("test.txt"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("verbose"), true);
+        let (name, sub_dict) = dict.get_subcommand().unwrap();
+        assert_eq!(name, "add");
+        assert_eq!(sub_dict.has_opt("force"), true);
+        assert_eq!(sub_dict.get_pos("file").unwrap(), "test.txt");
+
+        // Parse no subcommand
+        args = vec!(String::from("./test_exec"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_subcommand().is_none(), true);
+
+        // Parse an unknown subcommand
+        args = vec!(String::from("./test_exec"), String::from("mv"), String::from("test.txt"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Unknown subcommand 'mv'; expected one of [add, rm].");
+
+        // Errors in the subcommand are propagated
+        args = vec!(String::from("./test_exec"), String::from("rm"), String::from("--force"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+    }
+
+    #[test]
+    fn response_file_parser() {
+        // Write a response file
+        let path = std::env::temp_dir().join(format!("parse_args_test_response_file_{}.txt", std::process::id()));
+        std::fs::write(&path, "--opt1 test2\n  test3\n").unwrap();
+
+        // Create a parser with positionals and an option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_pos("pos2", "pos2", "Another test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+
+        // Parse with the file spliced in
+        let mut args = vec!(String::from("./test_exec"), String::from("test1"), format!("@{}", path.display()));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_opt("opt1").unwrap()[0], "test2");
+        assert_eq!(dict.get_pos("pos2").unwrap(), "test3");
+        std::fs::remove_file(&path).unwrap();
+
+        // Parse with a missing file
+        args = vec!(String::from("./test_exec"), String::from("@parse_args_non_existing_file.txt"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string().starts_with("Could not read argument file '@parse_args_non_existing_file.txt': "), true);
+
+        // Parse with response files disabled
+        parser.disable_response_files();
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "@parse_args_non_existing_file.txt");
+    }
+
+    #[test]
+    fn dash_pos_parser() {
+        // Create a parser with one positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("input", "input", "The input file, or '-' for stdin.");
+
+        // Parse a lone dash
+        let args = vec!(String::from("./test_exec"), String::from("-"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("input").unwrap(), "-");
+    }
+
+    #[test]
+    fn multi_pos_parser() {
+        // Create a parser with a normal and a variadic positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("cmd", "cmd", "The command to run.");
+        parser.add_pos_multi("args", "args", "The arguments to the command.");
+        parser.add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.");
+        parser.add_double_dash();
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [options] <cmd> <args...>");
+
+        // Parse with options interleaved
+        let mut args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("-o"), String::from("test3"), String::from("--"), String::from("-o"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("cmd").unwrap(), "test1");
+        assert_eq!(dict.has_opt("opt1"), true);
+        assert_eq!(dict.get_pos_multi("args").unwrap(), &vec!(String::from("test2"), String::from("test3"), String::from("-o")));
+
+        // Parse without any trailing values
+        args = vec!(String::from("./test_exec"), String::from("test1"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_pos("args"), false);
+        assert_eq!(dict.get_pos_multi("args"), None);
+    }
+
+    #[test]
+    fn multi_pos_not_last() {
+        // Create a parser with a variadic positional followed by a normal one
+        let mut parser = ArgParser::new();
+        parser.add_pos_multi("files", "files", "The input files.");
+        parser.add_pos("output", "output", "The output file.");
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec <files...> <output>");
+
+        // The last value is reserved for the normal positional
+        let mut args = vec!(String::from("./test_exec"), String::from("a"), String::from("b"), String::from("c"), String::from("out"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos_multi("files").unwrap(), &vec!(String::from("a"), String::from("b"), String::from("c")));
+        assert_eq!(dict.get_pos("output").unwrap(), "out");
+
+        // Even if that leaves nothing for the variadic one
+        args = vec!(String::from("./test_exec"), String::from("out"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_pos_multi("files"), None);
+        assert_eq!(dict.get_pos("output").unwrap(), "out");
+    }
+
+    #[test]
+    #[should_panic]
+    fn multi_pos_twice() {
+        // Only one variadic positional is allowed
+        let mut parser = ArgParser::new();
+        parser.add_pos_multi("args", "args", "The arguments.");
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_pos_multi("more_args", "more_args", "More arguments.");
+    }
+
+    #[test]
+    #[should_panic]
+    fn optional_pos_after_multi() {
+        // Optional positionals after a variadic one are ambiguous
+        let mut parser = ArgParser::new();
+        parser.add_pos_multi("args", "args", "The arguments.");
+        parser.add_pos_optional("pos1", "pos1", "A test positional.");
+    }
+
+    #[test]
+    fn count_opt_parser() {
+        // Create a parser with a counted flag
+        let mut parser = ArgParser::new();
+        parser.add_opt_count("verbose", "v", "verbose", "Increases the verbosity.");
+
+        // Parse the flag not at all
+        let mut args = vec!(String::from("./test_exec"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_count("verbose"), 0);
+
+        // Parse the flag repeated in a single argument
+        args = vec!(String::from("./test_exec"), String::from("-vvv"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_count("verbose"), 3);
+        assert_eq!(dict.get_opt("verbose").unwrap().len(), 0);
+
+        // Parse the longname twice
+        args = vec!(String::from("./test_exec"), String::from("--verbose"), String::from("--verbose"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_count("verbose"), 2);
+
+        // Other characters are still illegal
+        args = vec!(String::from("./test_exec"), String::from("-vx"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+
+        // The help should note that it's repeatable
+        assert_eq!(parser.get_help("./test_exec", 20, 80).contains("May be repeated."), true);
+    }
+
+    #[test]
+    fn case_insensitive_parser() {
+        // Create a parser with an option
+        let mut parser = ArgParser::new();
+        parser.add_opt("output", "o", "output", 1, 1, "<file>", "The output file.");
+
+        // By default, the case matters
+        let args = vec!(String::from("./test_exec"), String::from("--OUTPUT"), String::from("test.txt"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Unknown option '--OUTPUT' (argument 1)");
+
+        // But not if told otherwise
+        parser.set_case_insensitive(true);
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("output").unwrap()[0], "test.txt");
+        dict = parser.parse(&vec!(String::from("./test_exec"), String::from("-O"), String::from("test.txt")));
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("output").unwrap()[0], "test.txt");
+    }
+
+    #[test]
+    fn write_output() {
+        // Create a parser with a positional and option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+
+        // Write the help to a buffer
+        let mut buffer: Vec<u8> = Vec::new();
+        parser.write_help(&mut buffer, "./test_exec", 20, 80).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "\nUsage: ./test_exec [options] <pos1>\n\n\nPositionals:\n  <pos1>            A test positional.\n\nOptions:\n  -o,--opt1 <value>\n                    A test option.\n\n");
+
+        // Write errors and warnings to a buffer
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("--opt2"));
+        let dict = parser.parse(&args);
+        let mut buffer: Vec<u8> = Vec::new();
+        dict.write_errors(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Unknown option '--opt2' (argument 3); did you mean '--opt1'?\n");
+        let mut buffer: Vec<u8> = Vec::new();
+        dict.write_warnings(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Skipping positional 'test2' (index 1)...\n");
+    }
+
+    #[test]
+    fn suggestions() {
+        // Create a parser with a few options
+        let mut parser = ArgParser::new();
+        parser.add_opt("verbose", "v", "verbose", 0, 0, "", "Be verbose.")
+              .add_opt("version", "", "version", 0, 0, "", "Show the version.")
+              .add_help();
+
+        // Parse a near-miss
+        let mut args = vec!(String::from("./test_exec"), String::from("--verbsoe"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Unknown option '--verbsoe' (argument 1); did you mean '--verbose'?");
+
+        // Parse something completely different
+        args = vec!(String::from("./test_exec"), String::from("--output-file"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Unknown option '--output-file' (argument 1); use '--help' to see an overview of accepted options.");
+    }
+
+    #[test]
+    fn negative_values() {
+        // Create a parser with an option that takes a number
+        let mut parser = ArgParser::new();
+        parser.add_opt("offset", "o", "offset", 1, 1, "<number>", "The offset to use.");
+
+        // Parse a negative number
+        let mut args = vec!(String::from("./test_exec"), String::from("-o"), String::from("-5"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("offset").unwrap()[0], "-5");
+        args = vec!(String::from("./test_exec"), String::from("--offset"), String::from("-0.5"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("offset").unwrap()[0], "-0.5");
+
+        // Parse an unknown option instead
+        args = vec!(String::from("./test_exec"), String::from("-o"), String::from("-x"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 2);
+
+        // Numbers that clash with an option are parsed as that option
+        parser.add_opt("five", "5", "five", 0, 0, "", "A numeric option.");
+        args = vec!(String::from("./test_exec"), String::from("-o"), String::from("-5"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+    }
+
+    #[test]
+    fn optional_pos_parser() {
+        // Create a parser with an optional positional in between required ones
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_pos_optional("pos2", "pos2", "An optional test positional.")
+              .add_pos("pos3", "pos3", "Another test positional.");
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec <pos1> [<pos2>] <pos3>");
+
+        // Parse without the optional one
+        let mut args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test3"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.has_pos("pos2"), false);
+        assert_eq!(dict.get_pos("pos3").unwrap(), "test3");
+
+        // Parse with the optional one
+        args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("test3"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_pos("pos2").unwrap(), "test2");
+        assert_eq!(dict.get_pos("pos3").unwrap(), "test3");
+    }
+
+    #[test]
+    fn alias_parser() {
+        // Create a parser with an option with an alias
+        let mut parser = ArgParser::new();
+        parser.add_opt("colour", "c", "colour", 1, 1, "<when>", "When to use colours.")
+              .add_alias("colour", "color");
+
+        // Parse the canonical form
+        let mut args = vec!(String::from("./test_exec"), String::from("--colour"), String::from("always"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("colour").unwrap()[0], "always");
+
+        // Parse the alias
+        args = vec!(String::from("./test_exec"), String::from("--color=never"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("colour").unwrap()[0], "never");
+
+        // Aliases are only listed in the help if asked
+        assert_eq!(parser.get_help("./test_exec", 30, 80).contains("-c,--colour <when>"), true);
+        parser.set_show_aliases(true);
+        assert_eq!(parser.get_help("./test_exec", 30, 80).contains("-c,--colour,--color <when>"), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn alias_conflict() {
+        // Aliases may not conflict with other longnames
+        let mut parser = ArgParser::new();
+        parser.add_opt("colour", "c", "colour", 1, 1, "<when>", "When to use colours.")
+              .add_opt("color", "", "color", 0, 0, "", "Whether to use colours.")
+              .add_alias("colour", "color");
+    }
+
+    #[test]
+    #[should_panic(expected = "A longname cannot be empty.")]
+    fn alias_empty() {
+        // Aliases may not be empty, since that would match every longname
+        let mut parser = ArgParser::new();
+        parser.add_opt("colour", "c", "colour", 1, 1, "<when>", "When to use colours.")
+              .add_alias("colour", "");
+    }
+
+    #[test]
+    #[should_panic(expected = "A longname cannot contain an equals sign: 'a=b'.")]
+    fn alias_equals() {
+        // Aliases may not contain an equals sign, since it separates the value
+        let mut parser = ArgParser::new();
+        parser.add_opt("colour", "c", "colour", 1, 1, "<when>", "When to use colours.")
+              .add_alias("colour", "a=b");
+    }
+
+    #[test]
+    fn map_opt_parser() {
+        // Create a parser with a map option and a positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt_map("define", "D", "define", "<key>=<value>", "Defines a variable.");
+
+        // Parse a few pairs
+        let mut args = vec!(String::from("./test_exec"), String::from("-D"), String::from("a=1"), String::from("test1"), String::from("--define"), String::from("b=2"), String::from("-Da=3=4"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        let map = dict.get_opt_map("define").unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("a").unwrap(), "3=4");
+        assert_eq!(map.get("b").unwrap(), "2");
+
+        // Parse something that isn't a pair
+        args = vec!(String::from("./test_exec"), String::from("-D"), String::from("foo"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string(), "Expected key=value for '--define' (argument 1), got 'foo'.");
+    }
+
+    #[test]
+    fn help_widths() {
+        // Create a parser with a long description
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional with a rather long description that will be wrapped.");
+
+        // Without a (valid) terminal width, the defaults are used (note that we pass the terminal width instead of setting 'COLUMNS', since other tests read the environment in parallel)
+        assert_eq!(parser.resolve_help_widths(None), (DEFAULT_INDENT_WIDTH, DEFAULT_LINE_WIDTH));
+        assert_eq!(parser.resolve_help_widths(Some("wide")), (DEFAULT_INDENT_WIDTH, DEFAULT_LINE_WIDTH));
+
+        // Use a narrow terminal
+        assert_eq!(parser.resolve_help_widths(Some(" 40\n")), (DEFAULT_INDENT_WIDTH, 40));
+        let help = parser.get_help_for_columns("./test_exec", Some("40"));
+        assert_eq!(help.contains("  <pos1>            A test positional \n                    with a rather long\n"), true);
+        assert_eq!(help.lines().all(|l| l.len() < 40), true);
+
+        // Override the widths, which ignores the terminal
+        parser.set_help_widths(12, 200);
+        assert_eq!(parser.resolve_help_widths(Some("40")), (12, 200));
+        assert_eq!(parser.get_help_for_columns("./test_exec", Some("40")).contains("  <pos1>    A test positional with a rather long description that will be wrapped.\n"), true);
+
+        // Tiny widths should not crash
+        parser.set_help_widths(20, 5);
+        parser.get_help_default("./test_exec");
+    }
+
+    #[test]
+    fn grouped_help() {
+        // Create a parser with a few grouped options
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "", "opt1", 0, 0, "", "A test option.")
+              .add_opt("opt2", "", "opt2", 0, 0, "", "Another test option.")
+              .add_opt("opt3", "", "opt3", 0, 0, "", "Yet another test option.")
+              .add_opt_group("Output options")
+              .add_opt_group("Debug options")
+              .set_opt_group("opt3", "Output options")
+              .set_opt_group("opt1", "Debug options");
+
+        // The help should list the groups in order
+        let help = parser.get_help("./test_exec", 20, 80);
+        let options_i = help.find("\nOptions:\n  --opt2").unwrap();
+        let output_i = help.find("\nOutput options:\n  --opt3").unwrap();
+        let debug_i = help.find("\nDebug options:\n  --opt1").unwrap();
+        assert_eq!(options_i < output_i && output_i < debug_i, true);
+    }
+
+    #[test]
+    fn partial_longnames() {
+        // Create a parser with options that share a prefix
+        let mut parser = ArgParser::new();
+        parser.add_opt("output", "o", "output", 1, 1, "<file>", "The output file.")
+              .add_opt("out", "", "out-dir", 1, 1, "<dir>", "The output directory.");
+
+        // A prefix of a longname is not a match (nor a crash)
+        let mut args = vec!(String::from("./test_exec"), String::from("--out"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(matches!(dict.get_errors()[0], ParseError::UnknownOption{ .. }), true);
+
+        // Neither is a longname with something else behind it
+        args = vec!(String::from("./test_exec"), String::from("--outputs"), String::from("x"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(matches!(dict.get_errors()[0], ParseError::UnknownOption{ .. }), true);
+
+        // But an equals sign behind it is
+        args = vec!(String::from("./test_exec"), String::from("--output=x"), String::from("--out-dir"), String::from("y"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("output").unwrap()[0], "x");
+        assert_eq!(dict.get_opt("out").unwrap()[0], "y");
+    }
+
+    #[test]
+    fn try_add() {
+        // Create a parser with a positional and an option
+        let mut parser = ArgParser::new();
+        assert_eq!(parser.try_add_pos("pos1", "pos1", "A test positional."), Ok(()));
+        assert_eq!(parser.try_add_opt("opt1", "o", "opt1", 0, 1, "", "A test option."), Ok(()));
+
+        // Check each of the error conditions
+        assert_eq!(parser.try_add_pos("pos1", "pos2", "Another test positional.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt1", "", "opt2", 0, 0, "", "Another test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt2", "o", "opt2", 0, 0, "", "Another test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt2", "", "opt1", 0, 0, "", "Another test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt2", "ab", "opt2", 0, 0, "", "Another test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt2", "", "opt2", 2, 1, "", "Another test option.").is_err(), true);
+        parser.add_pos_multi("args", "args", "The arguments.");
+        assert_eq!(parser.try_add_pos("pos2", "pos2", "Another test positional."), Ok(()));
+
+        // Nothing should have been registered by the failed attempts
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [options] <pos1> <args...> <pos2>");
+        assert_eq!(parser.try_add_opt("opt2", "", "opt2", 0, 0, "", "Another test option."), Ok(()));
+    }
+
+    #[test]
+    fn extra_pos_parser() {
+        // Create a parser with one positional and an option with values
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 0, 2, "", "A test option.");
+
+        // Parse more positionals than registered, interleaved with the option
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("-o"), String::from("test3"), String::from("test4"), String::from("test5"), String::from("-o"), String::from("test6"));
+        let dict = parser.parse(&args);
+
+        // Once a positional was skipped, the option should not take any values given separately anymore
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap(), &Vec::<String>::new());
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_warnings(), &vec!(
+            ParseWarning::ExtraPositional{ value: String::from("test2"), index: 1 },
+            ParseWarning::ExtraPositional{ value: String::from("test3"), index: 2 },
+            ParseWarning::ExtraPositional{ value: String::from("test4"), index: 3 },
+            ParseWarning::ExtraPositional{ value: String::from("test5"), index: 4 },
+            ParseWarning::ExtraPositional{ value: String::from("test6"), index: 5 },
+        ));
+        assert_eq!(dict.get_warnings()[0].to_string(), "Skipping positional 'test2' (index 1)...");
+
+        // Before that, the option should consume the values after it until full
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("test1"), String::from("test2"), String::from("test3"), String::from("-o"), String::from("test4"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("test1"), String::from("test2")));
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test3");
+        assert_eq!(dict.get_warnings(), &vec!(ParseWarning::ExtraPositional{ value: String::from("test4"), index: 1 }));
+    }
+
+    #[test]
+    fn get_opt_one() {
+        // Create a parser with an option with a value and a flag
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.")
+              .add_opt("opt2", "p", "opt2", 0, 0, "", "A test flag.")
+              .add_opt("opt3", "q", "opt3", 1, 1, "<value>", "Another test option.");
+
+        // Parse with the option and the flag, but not the other option
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("test1"), String::from("-p"));
+        let dict = parser.parse(&args);
+
+        // Check that we get the value of the option, but nothing for the flag or the absent option
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("opt1"), Some("test1"));
+        assert_eq!(dict.has_opt("opt2"), true);
+        assert_eq!(dict.get_opt_one("opt2"), None);
+        assert_eq!(dict.get_opt_one("opt3"), None);
+    }
+
+    #[test]
+    fn from_arg_dict() {
+        // Define a config struct that can be built from an ArgDict
+        struct Config {
+            input   : String,
+            threads : u32,
+            verbose : bool,
+        }
+        impl FromArgDict for Config {
+            fn from_dict(dict: &ArgDict) -> Result<Self, String> {
+                Ok(Config {
+                    input   : dict.require_pos_as("input")?,
+                    threads : dict.get_opt_as("threads").unwrap_or(Ok(1)).map_err(|err| format!("Invalid threads: {}", err))?,
+                    verbose : dict.has_opt("verbose"),
+                })
+            }
+        }
+
+        // Create a parser that matches it
+        let mut parser = ArgParser::new();
+        parser.add_pos("input", "input", "The input file.")
+              .add_opt("threads", "t", "threads", 1, 1, "<n>", "The number of threads.")
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "Be verbose.");
+
+        // Parse some arguments and convert them
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-t"), String::from("4"));
+        let config: Config = parser.parse(&args).to_struct().unwrap();
+        assert_eq!(config.input, "test1");
+        assert_eq!(config.threads, 4);
+        assert_eq!(config.verbose, false);
+
+        // Missing or malformed values should result in errors
+        let args = vec!(String::from("./test_exec"));
+        assert_eq!(parser.parse(&args).to_struct::<Config>().err().unwrap(), "Missing positional 'input'.");
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-t"), String::from("four"));
+        assert_eq!(parser.parse(&args).to_struct::<Config>().is_err(), true);
+    }
+
+    #[test]
+    fn stop_at_first_pos_parser() {
+        // Create a parser that stops at the first positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("command", "command", "The command to run.")
+              .add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.")
+              .add_double_dash()
+              .set_stop_at_first_positional(true);
+
+        // Parse with options both before and after the positional
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("run"), String::from("--flag"), String::from("x"), String::from("--"), String::from("-o"));
+        let dict = parser.parse(&args);
+
+        // Everything after the positional should be passed through as-is
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.has_opt("opt1"), true);
+        assert_eq!(dict.get_pos("command").unwrap(), "run");
+        assert_eq!(dict.get_trailing(), &vec!(String::from("--flag"), String::from("x"), String::from("--"), String::from("-o")));
+
+        // Without any positional, there is nothing trailing
+        let args = vec!(String::from("./test_exec"), String::from("-o"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_trailing().len(), 0);
+    }
+
+    #[test]
+    fn num_pos_parser() {
+        // Create a parser with a single positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+
+        // Parse three positionals
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("test3"));
+        let dict = parser.parse(&args);
+
+        // One should be filled, the other two skipped
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.num_pos(), 1);
+        assert_eq!(dict.num_skipped_pos(), 2);
+        assert_eq!(dict.get_warnings().len(), 2);
+
+        // Nothing is skipped if everything fits
+        let args = vec!(String::from("./test_exec"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.num_pos(), 1);
+        assert_eq!(dict.num_skipped_pos(), 0);
+    }
+
+    #[test]
+    fn strict_parser() {
+        // Create a strict parser with a single positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .set_strict(true);
+
+        // Parse one positional too many
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"));
+        let dict = parser.parse(&args);
+
+        // This should be an error instead of a warning
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnexpectedPositional{ value: String::from("test2"), index: 1 }));
+        assert_eq!(dict.get_errors()[0].to_string(), "Unexpected positional 'test2' (index 1).");
+        assert_eq!(dict.has_pos("pos1"), false);
+        assert_eq!(dict.num_pos(), 0);
+
+        // The right number of positionals is fine
+        let args = vec!(String::from("./test_exec"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+    }
+
+    #[test]
+    fn interleaved_opt_parser() {
+        // Create a parser with two options with values
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 0, 3, "", "A test option.")
+              .add_opt("opt2", "p", "opt2", 1, 2, "", "Another test option.")
+              .add_double_dash();
+
+        // Give the first option three times, interleaved with the other
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("a"), String::from("--opt2"), String::from("x"), String::from("--opt1"), String::from("b"), String::from("-p"), String::from("y"), String::from("-o"), String::from("c"));
+        let dict = parser.parse(&args);
+
+        // The values should be in command-line order
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("a"), String::from("b"), String::from("c")));
+        assert_eq!(dict.get_opt("opt2").unwrap(), &vec!(String::from("x"), String::from("y")));
+
+        // A double dash in between values should not count as one of them
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("a"), String::from("--"), String::from("-b"), String::from("c"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("a"), String::from("-b"), String::from("c")));
+    }
+
+    #[test]
+    fn opt_prefix_parser() {
+        // Create a parser that accepts both dashes and slashes
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("flag", "f", "flag", 0, 0, "", "A test flag.")
+              .add_opt("opt1", "o", "opt1", 1, 1, "", "A test option.")
+              .set_option_prefixes(vec!('-', '/'));
+
+        // Parse with a mix of both
+        let args = vec!(String::from("./test_exec"), String::from("/f"), String::from("--opt1"), String::from("test1"), String::from("test2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("flag"), true);
+        assert_eq!(dict.get_opt_one("opt1"), Some("test1"));
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test2");
+
+        // Double slashes should be longnames, including with a value
+        let args = vec!(String::from("./test_exec"), String::from("//flag"), String::from("//opt1=test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("flag"), true);
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("test1")));
+
+        // Unknown options are still reported
+        let args = vec!(String::from("./test_exec"), String::from("/x"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors()[0], ParseError::UnknownOption{ option: String::from("/x"), index: 1, help_hint: false, suggestion: None });
+
+        // With only slashes, dashes are just positionals
+        parser.set_option_prefixes(vec!('/'));
+        let args = vec!(String::from("./test_exec"), String::from("-f"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "-f");
+    }
+
+    #[test]
+    fn is_empty_parser() {
+        // Create a parser with a positional and help
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_help();
+
+        // Parse nothing
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.is_empty(), true);
+        assert_eq!(dict.has_any(), false);
+
+        // Parse a single positional
+        let args = vec!(String::from("./test_exec"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.is_empty(), false);
+        assert_eq!(dict.has_any(), true);
+
+        // Help counts as something too
+        let args = vec!(String::from("./test_exec"), String::from("--help"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.is_empty(), false);
+    }
+
+    #[test]
+    fn bare_double_dash_parser() {
+        // Create a parser without the double dash
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 0, 1, "", "A test option.");
+
+        // A bare double dash should simply be a positional
+        let args = vec!(String::from("./test_exec"), String::from("--"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "--");
+
+        // Or a value, if an option takes it
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("--"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("opt1"), Some("--"));
+    }
+
+    #[test]
+    fn explain_parser() {
+        // Create a parser with a bit of everything
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("output", "o", "output", 1, 1, "<file>", "A test option.")
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.")
+              .add_double_dash();
+
+        // Explain a mixed invocation
+        let args = vec!(String::from("./test_exec"), String::from("--verbose"), String::from("test1"), String::from("-o"), String::from("test2"), String::from("--unknown"), String::from("--"), String::from("-v"));
+        let trace = parser.parse_explain(&args);
+        assert_eq!(trace, vec!(
+            (String::from("--verbose"), String::from("option:--verbose")),
+            (String::from("test1"), String::from("positional:pos1")),
+            (String::from("-o"), String::from("option:-o")),
+            (String::from("test2"), String::from("value-of:-o")),
+            (String::from("--unknown"), String::from("unknown")),
+            (String::from("--"), String::from("separator")),
+            (String::from("-v"), String::from("skipped")),
+        ));
+    }
+
+    #[test]
+    fn value_terminator_parser() {
+        // Create a parser with a positional and an option with a terminator
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("files", "f", "files", 1, 10, "<file>...", "A test option.")
+              .set_value_terminator("files", String::from(";"));
+
+        // The terminator should stop the values before the positional
+        let args = vec!(String::from("./test_exec"), String::from("--files"), String::from("a"), String::from("b"), String::from("c"), String::from(";"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_opt("files").unwrap(), &vec!(String::from("a"), String::from("b"), String::from("c")));
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+
+        // Without it, the option takes everything
+        let args = vec!(String::from("./test_exec"), String::from("--files"), String::from("a"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt("files").unwrap(), &vec!(String::from("a"), String::from("test1")));
+        assert_eq!(dict.has_pos("pos1"), false);
+    }
+
+    #[test]
+    fn clone_parser() {
+        // Create a parser and clone it
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 1, 1, "", "A test option.");
+        let mut clone = parser.clone();
+
+        // Changing the clone should not change the original
+        clone.add_opt("opt2", "p", "opt2", 0, 0, "", "Another test option.");
+
+        // Parse with the clone
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-o"), String::from("test2"), String::from("-p"));
+        let dict = clone.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_opt_one("opt1"), Some("test2"));
+        assert_eq!(dict.has_opt("opt2"), true);
+        assert_eq!(parser.parse(&args).has_errors(), true);
+
+        // The dict can be cloned and debugged too
+        let copy = dict.clone();
+        assert_eq!(copy.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(format!("{:?}", copy).contains("test2"), true);
+    }
+
+    #[test]
+    fn wrap_text_test() {
+        // Wrap a long paragraph without and with an indent
+        let text = "The quick brown fox jumps over the lazy dog, after which it runs off into the forest to never be seen again.";
+        assert_eq!(wrap_text(text, 0, 40), "The quick brown fox jumps over the \nlazy dog, after which it runs off into\nthe forest to never be seen again.");
+        assert_eq!(wrap_text(text, 4, 40), "    The quick brown fox jumps over the\n    lazy dog, after which it runs off \n    into the forest to never be seen \n    again.");
+
+        // Newlines should be kept
+        assert_eq!(wrap_text("Hello\nthere", 2, 40), "  Hello\n  there");
+
+        // The words themselves can be iterated over too
+        let words: Vec<(&str, &str)> = WordIterator::new("Hello there\tworld").take(3).collect();
+        assert_eq!(words, vec!(("Hello", " "), ("there", "\t"), ("world", "\0")));
+
+        // The iterator should end after the end of the string
+        let words: Vec<(&str, &str)> = WordIterator::new("ab cd").collect();
+        assert_eq!(words, vec!(("ab", " "), ("cd", "\0")));
+        assert_eq!(WordIterator::new("").collect::<Vec<(&str, &str)>>(), vec!(("", "\0")));
+
+        // Words with multi-byte characters should be split on their graphemes, not their bytes
+        let words: Vec<(&str, &str)> = WordIterator::new("Café naïve résumé").collect();
+        assert_eq!(words, vec!(("Café", " "), ("naïve", " "), ("résumé", "\0")));
+        assert_eq!(wrap_text("Café naïve résumé", 2, 40), "  Café naïve résumé");
+    }
+
+    #[test]
+    fn tab_width_help() {
+        // Create a parser with a description that contains tabs
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 0, 0, "", "A\ttest\toption.");
+
+        // By default, tabs stop every four columns
+        assert_eq!(parser.get_help("./test_exec", 10, 40), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -o,--opt1\n          A test    option.\n\n");
+
+        // But that can be changed
+        parser.set_tab_width(8);
+        assert_eq!(parser.get_help("./test_exec", 10, 40), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -o,--opt1\n          A     test    option.\n\n");
+    }
+
+    #[test]
+    fn duplicate_opt_parser() {
+        // Create a parser with a single-value option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("output", "o", "output", 1, 1, "<file>", "A test option.");
+
+        // Give it twice, in different ways
+        for args in vec!(
+            vec!(String::from("./test_exec"), String::from("--output"), String::from("a"), String::from("--output"), String::from("b")),
+            vec!(String::from("./test_exec"), String::from("-o"), String::from("a"), String::from("--output=b")),
+        ) {
+            let dict = parser.parse(&args);
+            assert_eq!(dict.has_warnings(), false);
+            assert_eq!(dict.get_errors(), &vec!(ParseError::DuplicateOption{ option: String::from("--output"), index: Some(3) }));
+            assert_eq!(dict.get_errors()[0].to_string(), "Option '--output' (argument 3) specified more than once.");
+        }
+
+        // Giving it once is fine
+        let args = vec!(String::from("./test_exec"), String::from("--output"), String::from("a"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("output"), Some("a"));
+    }
+
+    #[test]
+    fn greedy_values_parser() {
+        // Create a parser with an option that needs two values
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("pair", "p", "pair", 2, 2, "<a> <b>", "A test option.")
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.");
+
+        // By default, a flag in between means the option is short
+        let args = vec!(String::from("./test_exec"), String::from("--pair"), String::from("a"), String::from("-v"), String::from("b"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingValues{ option: String::from("--pair"), index: Some(1), expected: 2, got: 1 }));
+
+        // But when greedy, it takes the positional after it
+        parser.set_greedy_values(true);
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.get_opt("pair").unwrap(), &vec!(String::from("a"), String::from("b")));
+        assert_eq!(dict.has_opt("verbose"), true);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+    }
+
+    #[test]
+    fn exec_name() {
+        // Create a parser with a subcommand
+        let mut parser = ArgParser::new();
+        parser.add_subcommand("sub", ArgParser::new());
+
+        // Parse and check the executable names
+        let args = vec!(String::from("./test_exec"), String::from("sub"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.exec_name(), "./test_exec");
+        assert_eq!(dict.get_subcommand().unwrap().1.exec_name(), "./test_exec sub");
+        assert_eq!(parser.get_usage(dict.exec_name()), "Usage: ./test_exec <sub> ...");
+    }
+
+    #[test]
+    fn rest_opt_parser() {
+        // Create a parser with an option that takes the rest
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.")
+              .add_opt_rest("exec", "e", "exec", "The command to run.");
+
+        // Everything after it should be taken verbatim
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("--exec"), String::from("ls"), String::from("-la"), String::from("/tmp"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_opt("exec").unwrap(), &vec!(String::from("ls"), String::from("-la"), String::from("/tmp")));
+
+        // Including other options
+        let args = vec!(String::from("./test_exec"), String::from("-e"), String::from("-v"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("verbose"), false);
+        assert_eq!(dict.has_pos("pos1"), false);
+        assert_eq!(dict.get_opt("exec").unwrap(), &vec!(String::from("-v"), String::from("test1")));
+    }
+
+    #[test]
+    fn custom_messages() {
+        // Create a parser with translated messages
+        let mut parser = ArgParser::new();
+        let mut messages = Messages::default();
+        messages.unknown_option     = String::from("Onbekende optie '{0}'{1}");
+        messages.help_hint          = String::from("; gebruik '--help' voor een overzicht.");
+        messages.skipped_positional = String::from("Positioneel argument '{0}' ({1}) overgeslagen");
+        parser.add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.")
+              .add_help()
+              .set_messages(messages);
+
+        // Check that the errors use them
+        let args = vec!(String::from("./test_exec"), String::from("-x"));
+        let dict = parser.parse(&args);
+        let mut buffer: Vec<u8> = Vec::new();
+        dict.write_errors(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Onbekende optie '-x'; gebruik '--help' voor een overzicht.\n");
+        // The errors themselves still display in English
+        assert_eq!(dict.get_errors()[0].to_string(), "Unknown option '-x' (argument 1); use '--help' to see an overview of accepted options.");
+
+        // Check that the warnings use them
+        let args = vec!(String::from("./test_exec"), String::from("test1"));
+        let dict = parser.parse(&args);
+        let mut buffer: Vec<u8> = Vec::new();
+        dict.write_warnings(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Positioneel argument 'test1' (0) overgeslagen\n");
+
+        // Unknown placeholders are left alone, and values are not filled in again
+        assert_eq!(Messages::fill("{0} {2} {x} {", &["{1}", "a"]), "{1} {2} {x} {");
+    }
+
+    #[test]
+    fn merge_dicts() {
+        // Create a parser with a positional and some options
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 0, 2, "", "A test option.")
+              .add_opt("opt2", "p", "opt2", 1, 1, "", "Another test option.");
+
+        // Parse some defaults and the actual command line
+        let defaults = vec!(String::from("defaults"), String::from("default1"), String::from("-o"), String::from("a"), String::from("b"), String::from("-p"), String::from("c"));
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-o"), String::from("d"), String::from("e"), String::from("test2"));
+        let mut dict = parser.parse(&defaults);
+        dict.merge(parser.parse(&args));
+
+        // The command line should take precedence, replacing values instead of appending them
+        assert_eq!(dict.exec_name(), "./test_exec");
+        assert_eq!(dict.get_pos("pos1").unwrap(), "test1");
+        assert_eq!(dict.get_opt("opt1").unwrap(), &vec!(String::from("d"), String::from("e")));
+        assert_eq!(dict.get_opt_one("opt2"), Some("c"));
+        assert_eq!(dict.get_warnings().len(), 1);
+        assert_eq!(dict.num_skipped_pos(), 1);
+    }
+
+    #[test]
+    fn error_indices() {
+        // Create a parser with a flag and a subcommand
+        let mut sub = ArgParser::new();
+        sub.add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.");
+        let mut parser = ArgParser::new();
+        parser.add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.")
+              .add_subcommand("sub", sub);
+
+        // The index of the offending argument should be reported
+        let args = vec!(String::from("./test_exec"), String::from("-v"), String::from("--verbose=x"), String::from("--foo"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors()[0].to_string(), "Option '--verbose' (argument 2) cannot accept values (is passed 'x').");
+        assert_eq!(dict.get_errors()[1].to_string(), "Unknown option '--foo' (argument 3)");
+
+        // Also for errors in subcommands
+        let args = vec!(String::from("./test_exec"), String::from("-v"), String::from("sub"), String::from("-o"), String::from("--bar"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnknownOption{ option: String::from("--bar"), index: 4, help_hint: false, suggestion: None }));
+    }
+
+    #[test]
+    fn defs() {
+        // Create a parser with a positional and an option
+        let mut parser = ArgParser::new();
+        parser.add_pos_optional("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 1, 3, "<value>...", "A test option.")
+              .add_alias("opt1", "option1");
+
+        // Read them back
+        let pos = parser.pos_defs();
+        assert_eq!(pos.len(), 1);
+        assert_eq!(pos[0].uid, "pos1");
+        assert_eq!(pos[0].index, 0);
+        assert_eq!(pos[0].optional, true);
+        let opts = parser.opt_defs();
+        assert_eq!(opts.len(), 1);
+        assert_eq!(opts[0].uid, "opt1");
+        assert_eq!(opts[0].shortname, "o");
+        assert_eq!(opts[0].longname, "opt1");
+        assert_eq!(opts[0].aliases, &[String::from("option1")]);
+        assert_eq!(opts[0].min_n_values, 1);
+        assert_eq!(opts[0].max_n_values, 3);
+        assert_eq!(opts[0].description, "A test option.");
+    }
+
+    #[test]
+    fn bash_completion() {
+        // Create a parser with some options and a subcommand
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 0, 0, "", "A test option.")
+              .add_flag("color", "", "color", "A test flag.")
+              .add_alias("opt1", "option1")
+              .add_subcommand("sub", ArgParser::new())
+              .add_help();
+
+        // Generate the script
+        let script = parser.generate_bash_completion("./test-exec");
+        assert_eq!(script, "_test_exec() {\n    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n    COMPREPLY=( $(compgen -W \"--opt1 --option1 --color --no-color --help sub\" -- \"${cur}\") )\n}\ncomplete -F _test_exec test-exec\n");
+        for longname in vec!("--opt1", "--option1", "--color", "--no-color", "--help") {
+            assert_eq!(script.contains(longname), true);
+        }
+    }
+
+    #[test]
+    fn degenerate_longnames() {
+        // Create a parser with an option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.")
+              .add_opt("opt1", "o", "opt1", 1, 1, "", "A test option.");
+
+        // A value without a name is an error
+        let args = vec!(String::from("./test_exec"), String::from("--=x"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingOptionName{ index: 1, value: String::from("x") }));
+        assert_eq!(dict.get_errors()[0].to_string(), "Missing option name before '=' (argument 1).");
+
+        // A stray double dash without it being enabled is just a positional
+        let args = vec!(String::from("./test_exec"), String::from("--"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("pos1").unwrap(), "--");
+
+        // Options cannot be registered with empty longnames or equals signs in them
+        assert_eq!(parser.try_add_opt("opt2", "p", "", 0, 0, "", "Another test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("opt2", "p", "opt=2", 0, 0, "", "Another test option.").is_err(), true);
+    }
+
+    #[test]
+    fn parse_env() {
+        // Create a parser that accepts anything as positionals
+        let mut parser = ArgParser::new();
+        parser.add_pos_multi("args", "args", "Any arguments.");
+
+        // Parse whatever the test was called with
+        let dict = parser.parse_env();
+        assert_eq!(dict.exec_name(), std::env::args().next().unwrap());
+    }
+
+    #[test]
+    fn args_from_env() {
+        // The macro should be usable as an expression halfway through a function
+        let args = get_args_from_env!();
+        assert_eq!(args.len() >= 1, true);
+        assert_eq!(args, std::env::args().collect::<Vec<String>>());
+    }
+
+    #[test]
+    fn defaults_file() {
+        // Create a parser with some options
+        let mut parser = ArgParser::new();
+        parser.add_opt("output", "o", "output", 1, 1, "<file>", "A test option.")
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.")
+              .add_opt("threads", "t", "threads", 1, 1, "<n>", "Another test option.");
+
+        // Write a defaults file
+        let path = std::env::temp_dir().join(format!("parse_args_defaults_{}.txt", std::process::id()));
+        std::fs::write(&path, "# Some defaults\noutput = out.txt\n\nverbose = true\n").unwrap();
+        assert_eq!(parser.load_defaults(path.to_str().unwrap()), Ok(()));
+
+        // The defaults should be used if the options aren't given
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("output"), Some("out.txt"));
+        assert_eq!(dict.has_opt("verbose"), true);
+        assert_eq!(dict.has_opt("threads"), false);
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from("other.txt"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt_one("output"), Some("other.txt"));
+
+        // Unknown keys should be reported
+        std::fs::write(&path, "output = out.txt\nfoo = 1\nbar = 2\n").unwrap();
+        assert_eq!(parser.load_defaults(path.to_str().unwrap()), Err(format!("Invalid defaults file '{}': unknown options 'foo', 'bar'.", path.to_str().unwrap())));
+
+        // A file with problems should not change any of the defaults
+        std::fs::write(&path, "threads = 4
+verbose = maybe
+").unwrap();
+        assert_eq!(parser.load_defaults(path.to_str().unwrap()).is_err(), true);
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_opt("threads"), false);
+        assert_eq!(dict.has_opt("verbose"), true);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn validated_pos_parser() {
+        // Create a parser with a positional with choices and one with a validator
+        let mut parser = ArgParser::new();
+        parser.add_pos_choices("mode", "mode", "The mode to run in.", vec!(String::from("debug"), String::from("release")))
+              .add_pos_validated("count", "count", "The number of runs.", |value| match value.parse::<u32>() {
+                  Ok(_)    => Ok(()),
+                  Err(err) => Err(err.to_string()),
+              });
+        assert_eq!(parser.get_help("./test_exec", 20, 80).contains("Choices: debug, release"), true);
+
+        // Valid values should be accepted
+        let args = vec!(String::from("./test_exec"), String::from("debug"), String::from("3"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_pos("mode").unwrap(), "debug");
+
+        // Others should not
+        let args = vec!(String::from("./test_exec"), String::from("test"), String::from("three"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(
+            ParseError::InvalidChoice{ option: String::from("<mode>"), index: Some(1), value: String::from("test"), choices: vec!(String::from("debug"), String::from("release")) },
+            ParseError::InvalidValue{ option: String::from("<count>"), index: Some(2), value: String::from("three"), reason: String::from("invalid digit found in string") },
+        ));
+        assert_eq!(dict.get_errors()[1].to_string(), "Invalid value 'three' for '<count>' (argument 2): invalid digit found in string");
+        assert_eq!(dict.has_pos("mode"), false);
+    }
+
+    #[test]
+    fn warning_kinds() {
+        // Create a parser with a single positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+
+        // Parse one too many, and check that we can match on the warning
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_warnings().len(), 1);
+        match &dict.get_warnings()[0] {
+            ParseWarning::ExtraPositional{ value, index } => {
+                assert_eq!(value, "test2");
+                assert_eq!(*index, 1);
+            },
+        }
+    }
+
+    #[test]
+    fn short_equals_parser() {
+        // Create a parser with a single-value option and a flag
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+        parser.add_opt("flag1", "f", "flag1", 0, 0, "", "A test flag.");
+
+        // The value after the equals sign should be taken
+        let args = vec!(String::from("./test_exec"), String::from("-o=value"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1"), Some(&vec!(String::from("value"))));
+
+        // The attached form still works as before
+        let args = vec!(String::from("./test_exec"), String::from("-ovalue"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt("opt1"), Some(&vec!(String::from("value"))));
+
+        // Flags still reject it
+        let args = vec!(String::from("./test_exec"), String::from("-f=value"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnexpectedValue{ option: String::from("-f"), index: 1, value: String::from("value") }));
+    }
+
+    #[test]
+    fn get_opt_one_or() {
+        // Create a parser with two options
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+        parser.add_opt("opt2", "p", "opt2", 1, 1, "<value>", "A test option.");
+
+        // Give only the first
+        let args = vec!(String::from("./test_exec"), String::from("--opt1"), String::from("value"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt_one_or("opt1", "fallback"), "value");
+        assert_eq!(dict.get_opt_one_or("opt2", "fallback"), "fallback");
+    }
+
+    #[test]
+    fn help_conflict_after() {
+        // Registering an option that reuses the help names after the help should fail
+        let mut parser = ArgParser::new();
+        parser.add_help();
+        assert_eq!(parser.try_add_opt("hosts", "h", "hosts", 1, 1, "<host>", "A test option.").is_err(), true);
+        assert_eq!(parser.try_add_opt("show_help", "s", "help", 0, 0, "", "A test flag.").is_err(), true);
+        assert_eq!(parser.try_add_opt("hosts", "H", "hosts", 1, 1, "<host>", "A test option.").is_ok(), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn help_conflict_before() {
+        // Registering the help after an option that reuses its shortname should fail too
+        let mut parser = ArgParser::new();
+        parser.add_opt("hosts", "h", "hosts", 1, 1, "<host>", "A test option.");
+        parser.add_help();
+    }
+
+    #[test]
+    fn pos_by_index() {
+        // Create a parser with two positionals
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_pos("pos2", "pos2", "A test positional.");
+
+        // Fetch them by index rather than uid
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_pos_by_index(0), Some("test1"));
+        assert_eq!(dict.get_pos_by_index(1), Some("test2"));
+        assert_eq!(dict.get_pos_by_index(2), None);
+    }
+
+    #[test]
+    fn reconstruct() {
+        // Create a parser with a bit of everything
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_pos_multi("pos2", "pos2", "A test positional.");
+        parser.add_opt("output", "o", "output", 1, 1, "<file>", "A test option.");
+        parser.add_opt("files", "f", "files", 1, 3, "<files>", "A test option.");
+        parser.add_opt_count("verbose", "v", "verbose", "A test option.");
+        parser.add_flag("color", "", "color", "A test flag.");
+
+        // Parse some arguments and render them again
+        let args = vec!(String::from("./test_exec"), String::from("-vv"), String::from("test1"), String::from("test2"), String::from("-o"), String::from("foo"), String::from("--no-color"), String::from("test3"), String::from("--files"), String::from("a"), String::from("b"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let line = dict.reconstruct();
+        assert_eq!(line, "test1 test2 test3 --files a --files b --output foo --verbose --verbose --no-color");
+
+        // Parsing it again should give the same dict
+        let mut args2 = vec!(String::from("./test_exec"));
+        args2.extend(line.split_whitespace().map(|w| String::from(w)));
+        let dict2 = parser.parse(&args2);
+        assert_eq!(dict2.has_errors(), false);
+        assert_eq!(dict2.get_pos("pos1"), dict.get_pos("pos1"));
+        assert_eq!(dict2.get_pos_multi("pos2"), dict.get_pos_multi("pos2"));
+        assert_eq!(dict2.get_opt("output"), dict.get_opt("output"));
+        assert_eq!(dict2.get_opt("files"), dict.get_opt("files"));
+        assert_eq!(dict2.get_count("verbose"), 2);
+        assert_eq!(dict2.get_flag("color"), Some(false));
+
+        // Values that the shell would split are quoted
+        let args = vec!(String::from("./test_exec"), String::from("hello world"), String::from("--output"), String::from("it's"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.reconstruct(), "'hello world' --output 'it'\\''s'");
+
+        // Values that look like options are attached to their option
+        let args = vec!(String::from("./test_exec"), String::from("--output=-x"), String::from("posval"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let line = dict.reconstruct();
+        assert_eq!(line, "posval --output=-x");
+        let mut args2 = vec!(String::from("./test_exec"));
+        args2.extend(line.split_whitespace().map(|w| String::from(w)));
+        let dict2 = parser.parse(&args2);
+        assert_eq!(dict2.has_errors(), false);
+        assert_eq!(dict2.get_pos("pos1"), Some("posval"));
+        assert_eq!(dict2.get_opt("output"), Some(&vec!(String::from("-x"))));
+
+        // Options before a subcommand should not take its name
+        let mut sub = ArgParser::new();
+        sub.add_opt("force", "f", "force", 0, 0, "", "A test flag.");
+        let mut parser = ArgParser::new();
+        parser.add_opt("include", "I", "include", 1, 3, "<dir>", "A test option.")
+              .add_opt("aaa", "v", "aaa", 0, 0, "", "A test flag.")
+              .add_subcommand("build", sub);
+        let args = vec!(String::from("./test_exec"), String::from("-I"), String::from("a"), String::from("-v"), String::from("build"), String::from("-f"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let line = dict.reconstruct();
+        assert_eq!(line, "--include a --aaa build --force");
+        let mut args2 = vec!(String::from("./test_exec"));
+        args2.extend(line.split_whitespace().map(|w| String::from(w)));
+        let dict2 = parser.parse(&args2);
+        assert_eq!(dict2.has_errors(), false);
+        assert_eq!(dict2.get_opt("include"), Some(&vec!(String::from("a"))));
+        assert_eq!(dict2.has_opt("aaa"), true);
+        assert_eq!(dict2.get_subcommand().map(|(name, sub)| (name, sub.has_opt("force"))), Some(("build", true)));
+
+        // If no option is full, the last one is ended with its terminator instead
+        parser.set_value_terminator("include", String::from(";"));
+        let args = vec!(String::from("./test_exec"), String::from("-I"), String::from("a"), String::from(";"), String::from("build"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let line = dict.reconstruct();
+        assert_eq!(line, "--include a ';' build");
+        let args2 = vec!(String::from("./test_exec"), String::from("--include"), String::from("a"), String::from(";"), String::from("build"));
+        let dict2 = parser.parse(&args2);
+        assert_eq!(dict2.get_opt("include"), Some(&vec!(String::from("a"))));
+        assert_eq!(dict2.get_subcommand().map(|(name, _)| name), Some("build"));
+    }
+
+    #[test]
+    fn usage_options_label() {
+        // Create a parser with an option and a positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [options] <pos1>");
+
+        // Replace the label
+        parser.set_usage_options_label(Some(String::from("[flags...]")));
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [flags...] <pos1>");
+
+        // Suppress it
+        parser.set_usage_options_label(None);
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec <pos1>");
+    }
+
+    #[test]
+    fn short_alias_parser() {
+        // Create a parser with help that may also be given as '-?'
+        let mut parser = ArgParser::new();
+        parser.add_help();
+        parser.add_short_alias(HELP_UID, "?");
+
+        // Both should trigger the help
+        for arg in vec!("-h", "-?") {
+            let args = vec!(String::from("./test_exec"), String::from(arg));
+            let dict = parser.parse(&args);
+            assert_eq!(dict.has_errors(), false);
+            assert_eq!(dict.has_help(), true);
+        }
+
+        // The short alias is listed with the aliases
+        parser.set_show_aliases(true);
+        assert_eq!(parser.get_help("./test_exec", 20, 80).contains("-h,-?,--help"), true);
+
+        // Conflicts are detected both ways
+        assert_eq!(parser.try_add_opt("query", "?", "query", 1, 1, "<value>", "A test option.").is_err(), true);
+    }
+
+    #[test]
+    fn help_suppresses_errors() {
+        // Create a parser with help
+        let mut parser = ArgParser::new();
+        parser.add_help();
+
+        // By default, the unknown option is swallowed by the help
+        let args = vec!(String::from("./test_exec"), String::from("--help"), String::from("--unknown"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_help(), true);
+        assert_eq!(dict.has_errors(), false);
+
+        // But not if told otherwise
+        parser.set_help_suppresses_errors(false);
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_help(), true);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnknownOption{ option: String::from("--unknown"), index: 2, help_hint: true, suggestion: None }));
+    }
+
+    #[test]
+    fn opt_tuples() {
+        // Create a parser with options of different arities
+        let mut parser = ArgParser::new();
+        parser.add_opt("point", "p", "point", 2, 2, "<x> <y>", "A test option.");
+        parser.add_opt("color", "c", "color", 3, 3, "<r> <g> <b>", "A test option.");
+        parser.add_opt("name", "n", "name", 1, 1, "<name>", "A test option.");
+
+        // Get them as tuples
+        let args = vec!(String::from("./test_exec"), String::from("--point"), String::from("1"), String::from("2"), String::from("--color"), String::from("3"), String::from("4"), String::from("5"), String::from("--name"), String::from("test"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt_tuple2("point"), Some(("1", "2")));
+        assert_eq!(dict.get_opt_tuple3("color"), Some(("3", "4", "5")));
+
+        // The wrong arity or a missing option results in none
+        assert_eq!(dict.get_opt_tuple2("name"), None);
+        assert_eq!(dict.get_opt_tuple3("point"), None);
+        assert_eq!(dict.get_opt_tuple2("unknown"), None);
+    }
+
+    #[test]
+    fn get_opt_each() {
+        // Create a parser with an option that may be repeated
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("header", "H", "header", 1, 8, "<header>", "A test option.");
+
+        // Each value should be yielded, in order
+        let args = vec!(String::from("./test_exec"), String::from("--header"), String::from("a"), String::from("-H"), String::from("b"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_each("header").collect::<Vec<&str>>(), vec!("a", "b"));
+
+        // Nothing is yielded for options that aren't given
+        let args = vec!(String::from("./test_exec"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt_each("header").count(), 0);
+    }
+
+    #[test]
+    fn empty_args_parser() {
+        // Create a parser with an option and a positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+
+        // Empty arguments should not hang; the first is a value, the second is skipped
+        let args = vec!(String::from("./test_exec"), String::from("-o"), String::from(""), String::from(""));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("opt1"), Some(&vec!(String::new())));
+        assert_eq!(dict.has_pos("pos1"), false);
+
+        // Same for empty arguments among the positionals
+        let args = vec!(String::from("./test_exec"), String::from(""), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_pos("pos1"), Some("test1"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_dict() {
+        // Create a parser with some arguments
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 2, "<values>", "A test option.");
+        parser.add_flag("flag1", "f", "flag1", "A test flag.");
+
+        // Serialize the result to JSON and back
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("--opt1"), String::from("a"), String::from("b"), String::from("--no-flag1"));
+        let dict = parser.parse(&args);
+        let json = serde_json::to_string(&dict).unwrap();
+        let dict2: ArgDict = serde_json::from_str(&json).unwrap();
+        assert_eq!(dict2.get_opt("opt1"), dict.get_opt("opt1"));
+        assert_eq!(dict2.get_pos("pos1"), Some("test1"));
+        assert_eq!(dict2.get_flag("flag1"), Some(false));
+        assert_eq!(dict2.exec_name(), "./test_exec");
+    }
+
+    #[test]
+    fn max_extra_pos_parser() {
+        // Create a parser with a single positional that allows one extra
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.set_max_extra_positionals(1);
+
+        // One extra is a warning
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_warnings().len(), 1);
+
+        // Two extra is a warning and an error
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("test2"), String::from("test3"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_warnings(), &vec!(ParseWarning::ExtraPositional{ value: String::from("test2"), index: 1 }));
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnexpectedPositional{ value: String::from("test3"), index: 2 }));
+    }
+
+    #[test]
+    fn clear_parser() {
+        // Create a populated parser
+        let mut parser = ArgParser::new();
+        parser.add_help();
+        parser.add_double_dash();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+
+        // Clear it and register something else
+        parser.clear();
+        assert_eq!(parser.pos_defs().len(), 0);
+        assert_eq!(parser.opt_defs().len(), 0);
+        parser.add_pos("pos2", "pos2", "A test positional.");
+        parser.add_opt("opt1", "p", "opt1", 0, 0, "", "A test flag.");
+        assert_eq!(parser.pos_defs()[0].index, 0);
+
+        // The old definitions should be gone
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-p"), String::from("--help"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_pos("pos2"), None);
+        assert_eq!(dict.get_errors()[0], ParseError::UnknownOption{ option: String::from("--help"), index: 3, help_hint: false, suggestion: None });
+
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("-p"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_pos("pos2"), Some("test1"));
+        assert_eq!(dict.has_opt("opt1"), true);
+    }
+
+    #[test]
+    fn dash_values_parser() {
+        // Create a parser with a message option and a flag
+        let mut parser = ArgParser::new();
+        parser.add_double_dash();
+        parser.add_opt("message", "m", "message", 1, 1, "<message>", "A test option.");
+        parser.add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.");
+
+        // By default, the flag is not a value
+        let args = vec!(String::from("./test_exec"), String::from("--message"), String::from("--verbose"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingValues{ option: String::from("--message"), index: Some(1), expected: 1, got: 0 }));
+
+        // But it is if allowed (and only for a single value)
+        parser.set_allow_dash_values("message", true);
+        let args = vec!(String::from("./test_exec"), String::from("--message"), String::from("--verbose"), String::from("-v"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("message"), Some(&vec!(String::from("--verbose"))));
+        assert_eq!(dict.has_opt("verbose"), true);
+
+        // The double dash is still a separator
+        let args = vec!(String::from("./test_exec"), String::from("--message"), String::from("--"), String::from("-v"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt("message"), Some(&vec!(String::from("-v"))));
+    }
+
+    #[test]
+    fn opt_sources() {
+        // Create a parser with options that have defaults or environment variables
+        let mut parser = ArgParser::new();
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+        parser.add_opt("opt2", "p", "opt2", 1, 1, "<value>", "A test option.");
+        parser.add_opt_env("opt3", "q", "opt3", 1, 1, "<value>", "A test option.", "PARSE_ARGS_TEST_OPT_SOURCES");
+        parser.add_opt("opt4", "r", "opt4", 1, 1, "<value>", "A test option.");
+        parser.set_default("opt1", "a");
+        parser.set_default("opt2", "b");
+        std::env::set_var("PARSE_ARGS_TEST_OPT_SOURCES", "c");
+
+        // Check that each reports where it came from
+        let args = vec!(String::from("./test_exec"), String::from("--opt1"), String::from("x"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.opt_source("opt1"), Some(Source::CommandLine));
+        assert_eq!(dict.opt_source("opt2"), Some(Source::Default));
+        assert_eq!(dict.opt_source("opt3"), Some(Source::Env));
+        assert_eq!(dict.opt_source("opt4"), None);
+    }
+
+    #[test]
+    fn post_validator_parser() {
+        // Create a parser where the start must come before the end
+        let mut parser = ArgParser::new();
+        parser.add_opt("start", "s", "start", 1, 1, "<n>", "A test option.");
+        parser.add_opt("end", "e", "end", 1, 1, "<n>", "A test option.");
+        parser.set_post_validator(Box::new(|dict: &ArgDict| {
+            let mut errors: Vec<String> = Vec::new();
+            if let (Some(Ok(start)), Some(Ok(end))) = (dict.get_opt_as::<i32>("start"), dict.get_opt_as::<i32>("end")) {
+                if start > end { errors.push(format!("--start ({}) must come before --end ({}).", start, end)); }
+            }
+            errors
+        }));
+
+        // A valid order passes
+        let args = vec!(String::from("./test_exec"), String::from("--start"), String::from("1"), String::from("--end"), String::from("2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("start"), Some("1"));
+
+        // An invalid one results in an error (and clears the values)
+        let args = vec!(String::from("./test_exec"), String::from("--start"), String::from("3"), String::from("--end"), String::from("2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::Custom{ message: String::from("--start (3) must come before --end (2).") }));
+        assert_eq!(dict.get_errors()[0].to_string(), "--start (3) must come before --end (2).");
+        assert_eq!(dict.has_opt("start"), false);
+    }
+
+    #[test]
+    fn auto_indent_help() {
+        // Create a parser with a long option signature
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 3, 3, "<opt1> <opt2> <opt3>", "A test option.");
+
+        // With a fixed indent, the option's description is put on the next line
+        assert_eq!(parser.get_help("./test_exec", 20, 80), "\nUsage: ./test_exec [options] <pos1>\n\n\nPositionals:\n  <pos1>            A test positional.\n\nOptions:\n  -o,--opt1 <opt1> <opt2> <opt3>\n                    A test option.\n\n");
+
+        // With an automatic one, everything fits
+        parser.set_auto_indent(true);
+        assert_eq!(parser.get_help("./test_exec", 20, 80), "\nUsage: ./test_exec [options] <pos1>\n\n\nPositionals:\n  <pos1>                           A test positional.\n\nOptions:\n  -o,--opt1 <opt1> <opt2> <opt3>   A test option.\n\n");
+    }
+
+    #[test]
+    fn has_opt_with_value() {
+        // Create a parser with a flag and an option with an optional value
+        let mut parser = ArgParser::new();
+        parser.add_opt("flag1", "f", "flag1", 0, 0, "", "A test flag.");
+        parser.add_opt("opt1", "o", "opt1", 0, 1, "[<value>]", "A test option.");
+        parser.add_opt("opt2", "p", "opt2", 0, 1, "[<value>]", "A test option.");
+
+        // Only the option given with a value should have one
+        let args = vec!(String::from("./test_exec"), String::from("-f"), String::from("-o"), String::from("test"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_opt("flag1"), true);
+        assert_eq!(dict.has_opt_with_value("flag1"), false);
+        assert_eq!(dict.has_opt_with_value("opt1"), true);
+        assert_eq!(dict.has_opt_with_value("opt2"), false);
+    }
+
+    #[test]
+    fn annotated_help() {
+        // Create a parser with an option that has a default and choices
+        let mut parser = ArgParser::new();
+        parser.add_opt_choices("color", "c", "color", 1, 1, "<color>", "The color to use.", vec!(String::from("red"), String::from("green"), String::from("blue")));
+        parser.set_default("color", "red");
+
+        // By default, only the choices are shown
+        assert_eq!(parser.get_help("./test_exec", 20, 80), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -c,--color <color>\n                    The color to use.\n                    Choices: red, green, blue\n\n");
+
+        // If told to, both are appended to the description and wrapped
+        parser.set_annotate_help(true);
+        assert_eq!(parser.get_help("./test_exec", 20, 100), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -c,--color <color>\n                    The color to use. (default: red) (choices: red, green, blue)\n\n");
+        assert_eq!(parser.get_help("./test_exec", 20, 50), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -c,--color <color>\n                    The color to use. (default: \n                    red) (choices: red, green, \n                    blue)\n\n");
+    }
+
+    #[test]
+    fn parse_os() {
+        // Create a parser with a positional and an option
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+
+        // Parsing OsStrings should give the same as parsing Strings
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("--opt1"), String::from("value"));
+        let os_args: Vec<std::ffi::OsString> = args.iter().map(|a| std::ffi::OsString::from(a)).collect();
+        let dict = parser.parse(&args);
+        let os_dict = parser.parse_os(&os_args);
+        assert_eq!(os_dict.has_errors(), false);
+        assert_eq!(os_dict.get_pos("pos1"), dict.get_pos("pos1"));
+        assert_eq!(os_dict.get_opt("opt1"), dict.get_opt("opt1"));
+    }
+
+    #[test]
+    fn collect_unknown_parser() {
+        // Create a parser that collects unknown options
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.");
+        parser.add_double_dash();
+        parser.set_collect_unknown(true);
+
+        // The unknown ones should be collected, and the known ones parsed as usual
+        let args = vec!(String::from("./test_exec"), String::from("test1"), String::from("--foo=bar"), String::from("-o"), String::from("value"), String::from("-x"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_unknown(), &vec!(String::from("--foo=bar"), String::from("-x")));
+        assert_eq!(dict.get_opt("opt1"), Some(&vec!(String::from("value"))));
+        assert_eq!(dict.get_pos("pos1"), Some("test1"));
+
+        // Values given separately should be collected with the unknown option, up to the next option or double dash
+        let args = vec!(String::from("./test_exec"), String::from("--foo"), String::from("bar"), String::from("baz"), String::from("-o"), String::from("value"), String::from("-x"), String::from("-1"), String::from("--"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_unknown(), &vec!(String::from("--foo"), String::from("bar"), String::from("baz"), String::from("-x"), String::from("-1")));
+        assert_eq!(dict.get_opt("opt1"), Some(&vec!(String::from("value"))));
+        assert_eq!(dict.get_pos("pos1"), Some("test1"));
+
+        // Values given inline should not take any of the following arguments
+        let args = vec!(String::from("./test_exec"), String::from("--foo=bar"), String::from("-xbaz"), String::from("test1"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_unknown(), &vec!(String::from("--foo=bar"), String::from("-xbaz")));
+        assert_eq!(dict.get_pos("pos1"), Some("test1"));
+    }
+
+    #[test]
+    fn int_range_opt_parser() {
+        // Create a parser with an integer option
+        let mut parser = ArgParser::new();
+        parser.add_opt_int_range("level", "l", "level", "<level>", "A test option.", 0, 10);
+
+        // Values in range are fine (including the bounds)
+        for value in vec!("0", "5", "10") {
+            let args = vec!(String::from("./test_exec"), String::from("--level"), String::from(value));
+            let dict = parser.parse(&args);
+            assert_eq!(dict.has_errors(), false);
+        }
+
+        // Values out of range are not
+        let args = vec!(String::from("./test_exec"), String::from("--level"), String::from("42"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::OutOfRange{ option: String::from("--level"), index: Some(1), value: 42, min: 0, max: 10 }));
+        assert_eq!(dict.get_errors()[0].to_string(), "Value 42 for '--level' (argument 1) out of range [0, 10].");
+
+        // And neither are values that aren't numbers
+        let args = vec!(String::from("./test_exec"), String::from("--level"), String::from("high"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::InvalidValue{ option: String::from("--level"), index: Some(1), value: String::from("high"), reason: String::from("invalid digit found in string") }));
+    }
+
+    #[test]
+    fn require_if_min_parser() {
+        // Create a parser with an option that needs two values and a flag
+        let mut parser = ArgParser::new();
+        parser.add_opt("point", "p", "point", 2, 2, "<x> <y>", "A test option.");
+        parser.add_opt("flag1", "f", "flag1", 0, 0, "", "A test flag.");
+
+        // By default, leaving it out is fine
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+
+        // But not if told otherwise (which doesn't affect the flag)
+        parser.set_require_if_min("point", true);
+        parser.set_require_if_min("flag1", true);
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingRequired{ option: String::from("--point") }));
+
+        // Giving it is still fine
+        let args = vec!(String::from("./test_exec"), String::from("--point"), String::from("1"), String::from("2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+    }
+
+    #[test]
+    fn arg_errors() {
+        // Create a parser with one positional
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+
+        // Parse a few unknown options, and use the result as a boxed error
+        let args = vec!(String::from("./test_exec"), String::from("--test1"), String::from("--test2"));
+        let run = || -> Result<ArgDict, Box<dyn std::error::Error>> { Ok(parser.try_parse(&args)?) };
+        let err = run().err().unwrap();
+        assert_eq!(err.to_string(), "Unknown option '--test1' (argument 1)\nUnknown option '--test2' (argument 2)");
+        assert_eq!(err.source().unwrap().to_string(), "Unknown option '--test1' (argument 1)");
+    }
+
+    #[test]
+    fn word_separators() {
+        // By default, a non-breaking space is not a separator, but a custom separator can be given
+        let words: Vec<(&str, &str)> = WordIterator::new("10\u{a0}km a/b").take(2).collect();
+        assert_eq!(words, vec!(("10\u{a0}km", " "), ("a/b", "\0")));
+        let words: Vec<(&str, &str)> = WordIterator::with_separators("10\u{a0}km a/b", &[" ", "/"]).take(3).collect();
+        assert_eq!(words, vec!(("10\u{a0}km", " "), ("a", "/"), ("b", "\0")));
+
+        // Create a parser with a description that has both
+        let mut parser = ArgParser::new();
+        parser.add_opt("path", "p", "path", 1, 1, "<path>", "Up to 10\u{a0}km from /usr/local/share/test.");
+        parser.set_word_separators(vec!(String::from(" "), String::from("/")));
+
+        // The non-breaking space should keep its words together, while the path may be broken after its slashes
+        assert_eq!(parser.get_help("./test_exec", 4, 20), "\nUsage: ./test_exec [options]\n\n\nPositionals:\n   <none>\n\nOptions:\n  -p,--path <path>\n    Up to 10\u{a0}km \n    from /usr/\n    local/share/\n    test.\n\n");
+    }
+
+    #[test]
+    fn summary() {
+        // Create a parser with some options, one with a multi-line description
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional.");
+        parser.add_opt("opt1", "o", "opt1", 1, 1, "<value>", "A test option.\nWith more details.");
+        parser.add_opt("verbose", "v", "verbose", 0, 0, "", "Another test option.");
+        parser.add_help();
+
+        // Each option should be listed once, with only its first line
+        assert_eq!(parser.get_summary("./test_exec"), "./test_exec options:\n  --opt1     A test option.\n  --verbose  Another test option.\n  --help     Shows this list of arguments, then quits.\n");
+
+        // Multibyte names should line up too
+        let mut parser = ArgParser::new();
+        parser.add_opt("naive", "", "naïve", 0, 0, "", "d1");
+        parser.add_opt("abcdef", "", "abcdef", 0, 0, "", "d2");
+        assert_eq!(parser.get_summary("./test_exec"), "./test_exec options:\n  --naïve   d1\n  --abcdef  d2\n");
+    }
+
+    #[test]
+    fn bool_opt_parser() {
+        // Create a parser with a boolean option
+        let mut parser = ArgParser::new();
+        parser.add_opt_bool("enabled", "e", "enabled", "A test option.");
+
+        // Check a few values
+        let args = vec!(String::from("./test_exec"), String::from("--enabled"), String::from("true"));
+        assert_eq!(parser.parse(&args).get_bool("enabled"), Some(Ok(true)));
+        let args = vec!(String::from("./test_exec"), String::from("--enabled"), String::from("No"));
+        assert_eq!(parser.parse(&args).get_bool("enabled"), Some(Ok(false)));
+        let args = vec!(String::from("./test_exec"), String::from("--enabled"), String::from("maybe"));
+        assert_eq!(parser.parse(&args).get_bool("enabled"), Some(Err(String::from("Invalid boolean 'maybe' for option '--enabled' (expected true/false, yes/no or 1/0)."))));
+
+        // Missing options have no value
+        let args = vec!(String::from("./test_exec"));
+        assert_eq!(parser.parse(&args).get_bool("enabled"), None);
+    }
+
+    #[test]
+    fn double_dash_index() {
+        // Create a parser with a flag and a variadic positional
+        let mut parser = ArgParser::new();
+        parser.add_double_dash();
+        parser.add_flag("a", "a", "all", "Does everything.");
+        parser.add_pos_multi("files", "<files>", "The files to process.");
+
+        // Check the index when it's given
+        let args = vec!(String::from("./test_exec"), String::from("-a"), String::from("--"), String::from("b"), String::from("c"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.double_dash_index(), Some(2));
+        assert_eq!(dict.get_pos_multi("files"), Some(&vec![String::from("b"), String::from("c")]));
+
+        // Check it's None if it isn't
+        let args = vec!(String::from("./test_exec"), String::from("-a"), String::from("b"), String::from("c"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.double_dash_index(), None);
+    }
+
+    #[test]
+    fn pos_metavars() {
+        // Create a parser with a couple of positionals
+        let mut parser = ArgParser::new();
+        parser.add_pos("input", "input", "The input file.")
+              .add_pos_optional("output", "output", "The output file.")
+              .set_metavar("input", String::from("FILE"));
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec <FILE> [<output>]");
+
+        // Try without brackets
+        parser.set_pos_bracket_style(BracketStyle::Plain);
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec FILE [output]");
+        assert_eq!(parser.get_help("./test_exec", 20, 80).contains("\n  FILE              The input file.\n"), true);
+
+        // Try with square brackets
+        parser.set_pos_bracket_style(BracketStyle::Square);
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec [FILE] [output]");
+    }
+
+    #[test]
+    fn partial_parser() {
+        // Create a parser with a flag and a positional
+        let mut parser = ArgParser::new();
+        parser.add_flag("all", "a", "all", "Does everything.")
+              .add_pos("file", "file", "The file to process.");
+
+        // Check that it stops at an unknown option
+        let args = vec!(String::from("./test_exec"), String::from("-a"), String::from("test"), String::from("--unknown"), String::from("value"), String::from("-a"));
+        let (dict, rest) = parser.parse_partial(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("all"), true);
+        assert_eq!(dict.get_pos("file"), Some("test"));
+        assert_eq!(rest, vec!(String::from("--unknown"), String::from("value"), String::from("-a")));
+
+        // Check that it stops at a superfluous positional
+        let args = vec!(String::from("./test_exec"), String::from("test"), String::from("extra"), String::from("-a"));
+        let (dict, rest) = parser.parse_partial(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_warnings(), false);
+        assert_eq!(dict.has_opt("all"), false);
+        assert_eq!(rest, vec!(String::from("extra"), String::from("-a")));
+
+        // Check that nothing remains if everything is parsed
+        let args = vec!(String::from("./test_exec"), String::from("test"), String::from("-a"));
+        let (dict, rest) = parser.parse_partial(&args);
+        assert_eq!(dict.has_opt("all"), true);
+        assert_eq!(rest.len(), 0);
+    }
+
+    #[test]
+    fn file_opt_parser() {
+        // Write a file with a secret
+        let path = std::env::temp_dir().join(format!("parse_args_test_file_opt_{}.txt", std::process::id()));
+        std::fs::write(&path, "hunter2\n").unwrap();
+
+        // Create a parser with the option
+        let mut parser = ArgParser::new();
+        parser.add_opt_file("password", "p", "password-file", "<path>", "The file with the password.", true);
+
+        // Parse with the file, with and without trimming
+        let args = vec!(String::from("./test_exec"), String::from("--password-file"), format!("{}", path.display()));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("password"), Some("hunter2"));
+        parser.add_opt_file("raw", "r", "raw-file", "<path>", "The file with the raw password.", false);
+        let args = vec!(String::from("./test_exec"), String::from("-r"), format!("{}", path.display()));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_opt_one("raw"), Some("hunter2\n"));
+        std::fs::remove_file(&path).unwrap();
+
+        // Parse with a missing file
+        let args = vec!(String::from("./test_exec"), String::from("--password-file"), String::from("parse_args_non_existing_file.txt"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors().len(), 1);
+        assert_eq!(dict.get_errors()[0].to_string().starts_with("Could not read file for '--password-file' (argument 1): "), true);
+    }
+
+    #[test]
+    fn trimmed_help() {
+        // Create a parser with a positional and an option
+        let mut parser = ArgParser::new();
+        parser.add_pos("file", "file", "The file to process.")
+              .add_flag("all", "a", "all", "Does everything.");
+
+        // By default, the help is surrounded by blank lines
+        let help = parser.get_help("./test_exec", 20, 80);
+        assert_eq!(help.starts_with("\nUsage: ./test_exec [options] <file>\n"), true);
+        assert_eq!(help.ends_with("\n\n"), true);
+
+        // But not if trimmed, which leaves the rest as-is
+        parser.set_help_trim(true);
+        let trimmed = parser.get_help("./test_exec", 20, 80);
+        assert_eq!(trimmed.starts_with("Usage: ./test_exec [options] <file>\n\n\nPositionals:\n"), true);
+        assert_eq!(trimmed.ends_with("\n"), true);
+        assert_eq!(trimmed.ends_with("\n\n"), false);
+        assert_eq!(format!("\n{}\n", trimmed), help);
+    }
+
+    #[test]
+    fn hidden_parser() {
+        // Create a parser with a hidden option and a normal one
+        let mut parser = ArgParser::new();
+        parser.add_opt("output", "o", "output", 1, 1, "<file>", "The output file.")
+              .add_opt_hidden("legacy", "l", "legacy-mode", 0, 0, "", "Uses the old behaviour.")
+              .add_flag("experimental", "x", "experimental", "Enables experimental features.")
+              .set_hidden("experimental", true);
+
+        // It's still parsed
+        let args = vec!(String::from("./test_exec"), String::from("--legacy-mode"), String::from("-x"), String::from("-o"), String::from("out.txt"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("legacy"), true);
+        assert_eq!(dict.has_opt("experimental"), true);
+
+        // But not shown
+        let help = parser.get_help("./test_exec", 20, 80);
+        assert_eq!(help.contains("--output"), true);
+        assert_eq!(help.contains("legacy"), false);
+        assert_eq!(help.contains("experimental"), false);
+        assert_eq!(parser.generate_bash_completion("./test_exec").contains("legacy"), false);
+        assert_eq!(parser.get_summary("./test_exec").contains("experimental"), false);
+        assert_eq!(parser.opt_defs().iter().filter(|o| o.hidden).count(), 2);
+
+        // The options label disappears from the usage if all options are hidden
+        parser.set_hidden("output", true);
+        assert_eq!(parser.get_usage("./test_exec"), "Usage: ./test_exec");
+    }
+
+    #[test]
+    fn required_one_of_parser() {
+        // Create a parser with a group of formats
+        let mut parser = ArgParser::new();
+        parser.add_flag("json", "j", "json", "Outputs JSON.")
+              .add_flag("yaml", "y", "yaml", "Outputs YAML.")
+              .add_required_one_of(vec!(String::from("json"), String::from("yaml")));
+
+        // Give none of them
+        let mut args = vec!(String::from("./test_exec"));
+        let mut dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingOneOf{ options: vec!(String::from("--json"), String::from("--yaml")) }));
+        assert_eq!(dict.get_errors()[0].to_string(), "One of '--json', '--yaml' is required.");
+
+        // Give one of them
+        args = vec!(String::from("./test_exec"), String::from("--yaml"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.has_opt("yaml"), true);
+
+        // Give both of them
+        args = vec!(String::from("./test_exec"), String::from("--json"), String::from("-y"));
+        dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MutuallyExclusive{ options: vec!(String::from("--json"), String::from("--yaml")) }));
+    }
+
+    #[test]
+    #[should_panic]
+    fn required_one_of_unknown() {
+        // Registering an unknown option in the group panics
+        let mut parser = ArgParser::new();
+        parser.add_flag("json", "j", "json", "Outputs JSON.")
+              .add_required_one_of(vec!(String::from("json"), String::from("xml")));
+    }
+
+    #[test]
+    fn help_json() {
+        // Create a parser with some positionals and options
+        let mut parser = ArgParser::new();
+        parser.add_pos("file", "file", "The \"file\" to process.")
+              .add_opt("tags", "t", "tags", 1, 3, "<tag>...", "The tags to use.\nMay be repeated.")
+              .add_opt_hidden("legacy", "", "legacy", 0, 0, "", "Uses the old behaviour.")
+              .add_help();
+
+        // Check the JSON contains everything
+        let json = parser.get_help_json();
+        assert_eq!(json.contains("{\"uid\":\"file\",\"index\":0,\"name\":\"file\",\"description\":\"The \\\"file\\\" to process.\",\"multi\":false,\"optional\":false,\"hidden\":false}"), true);
+        assert_eq!(json.contains("\"longname\":\"tags\",\"aliases\":[],\"short_aliases\":[],\"min_n_values\":1,\"max_n_values\":3,"), true);
+        assert_eq!(json.contains("\"description\":\"The tags to use.\\nMay be repeated.\""), true);
+        assert_eq!(json.contains("\"longname\":\"legacy\",\"aliases\":[],\"short_aliases\":[],\"min_n_values\":0,\"max_n_values\":0,"), true);
+        assert_eq!(json.contains("\"required\":false,\"hidden\":true}"), true);
+        assert_eq!(json.contains("\"longname\":\"help\""), true);
+        assert_eq!(json.ends_with("\"subcommands\":[]}"), true);
+
+        // Check that it's valid JSON too
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["options"][1]["longname"], "legacy");
+        assert_eq!(value["positionals"][0]["description"], "The \"file\" to process.");
+    }
+
+    #[test]
+    fn multibyte_long_opt_parser() {
+        // Create a parser with options that have multibyte names
+        let mut parser = ArgParser::new();
+        parser.add_opt("name", "n", "name", 1, 1, "<name>", "The name to use.")
+              .add_opt("naive", "", "naïve", 1, 1, "<value>", "A multibyte option.")
+              .add_flag("quiet", "q", "quiet", "Prints nothing.");
+
+        // Give multibyte values inline
+        let args = vec!(String::from("./test_exec"), String::from("--name=café"), String::from("--naïve=ü"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("name"), Some("café"));
+        assert_eq!(dict.get_opt_one("naive"), Some("ü"));
+
+        // Give a multibyte value to a flag
+        let args = vec!(String::from("./test_exec"), String::from("--quiet=é"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::UnexpectedValue{ option: String::from("--quiet"), index: 1, value: String::from("é") }));
+
+        // Do the same with a multibyte prefix
+        parser.set_option_prefixes(vec!('—'));
+        let args = vec!(String::from("./test_exec"), String::from("——name=café"), String::from("——naïve=ü"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt_one("name"), Some("café"));
+        assert_eq!(dict.get_opt_one("naive"), Some("ü"));
+    }
+
+    #[test]
+    fn repeatable_opt_parser() {
+        // Create a parser with a repeatable single-value option
+        let mut parser = ArgParser::new();
+        parser.add_opt("include", "I", "include", 1, 1, "<dir>", "Adds an include directory.")
+              .add_opt("pair", "p", "pair", 2, 2, "<a> <b>", "Adds a pair.")
+              .set_repeatable("include", true)
+              .set_repeatable("pair", true);
+
+        // Mix the attached and separate forms
+        let args = vec!(String::from("./test_exec"), String::from("-Ifoo"), String::from("-I"), String::from("bar"), String::from("--include=baz"), String::from("--include"), String::from("quz"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("include"), Some(&vec!(String::from("foo"), String::from("bar"), String::from("baz"), String::from("quz"))));
+
+        // The bounds apply to each occurrence
+        let args = vec!(String::from("./test_exec"), String::from("-p"), String::from("a"), String::from("b"), String::from("-p"), String::from("c"), String::from("d"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("pair").unwrap().len(), 4);
+        let args = vec!(String::from("./test_exec"), String::from("-p"), String::from("a"), String::from("b"), String::from("-p"), String::from("c"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingValues{ option: String::from("--pair"), index: Some(4), expected: 2, got: 1 }));
+
+        // That includes occurrences without any values
+        parser.add_opt("path", "P", "path", 1, 3, "<dir>...", "Adds up to three paths.")
+              .set_repeatable("path", true);
+        let args = vec!(String::from("./test_exec"), String::from("-P"), String::from("-P"), String::from("a"), String::from("b"), String::from("c"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingValues{ option: String::from("--path"), index: Some(1), expected: 1, got: 0 }));
+        let args = vec!(String::from("./test_exec"), String::from("-P"), String::from("a"), String::from("-P"), String::from("b"), String::from("c"), String::from("d"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        assert_eq!(dict.get_opt("path").unwrap().len(), 4);
+
+        // Without it, repeating is still an error
+        parser.set_repeatable("include", false);
+        let args = vec!(String::from("./test_exec"), String::from("-Ifoo"), String::from("-I"), String::from("bar"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::DuplicateOption{ option: String::from("--include"), index: Some(2) }));
+    }
+
+    #[test]
+    fn describe_opt() {
+        // Create a parser with some options
+        let mut parser = ArgParser::new();
+        parser.add_opt("output_file", "o", "output", 1, 1, "<file>", "The output file.")
+              .add_flag("quiet", "q", "quiet", "Prints nothing.")
+              .add_alias("output_file", "out");
+
+        // Look them up by uid, longname and shortname
+        assert_eq!(parser.describe_opt("output_file"), Some(String::from("The output file.")));
+        assert_eq!(parser.describe_opt("output"), Some(String::from("The output file.")));
+        assert_eq!(parser.describe_opt("--output"), Some(String::from("The output file.")));
+        assert_eq!(parser.describe_opt("--out"), Some(String::from("The output file.")));
+        assert_eq!(parser.describe_opt("q"), Some(String::from("Prints nothing.")));
+        assert_eq!(parser.describe_opt("-q"), Some(String::from("Prints nothing.")));
+
+        // Unknown names give nothing
+        assert_eq!(parser.describe_opt("--verbose"), None);
+        assert_eq!(parser.describe_opt("--"), None);
+        assert_eq!(parser.describe_opt(""), None);
+    }
+
+    #[test]
+    fn defaults_in_groups() {
+        // Create a parser with a defaulted option in a mutually exclusive group
+        let mut parser = ArgParser::new();
+        parser.add_opt("format", "f", "format", 1, 1, "<format>", "The output format.")
+              .add_flag("json", "j", "json", "Outputs JSON.")
+              .set_default("format", "text")
+              .add_mutex_group(vec!(String::from("format"), String::from("json")));
+
+        // The default shouldn't conflict with the other option
+        let args = vec!(String::from("./test_exec"), String::from("--json"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let args = vec!(String::from("./test_exec"), String::from("--json"), String::from("-f"), String::from("yaml"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MutuallyExclusive{ options: vec!(String::from("--format"), String::from("--json")) }));
+
+        // Create a parser with a defaulted option that requires another
+        let mut parser = ArgParser::new();
+        parser.add_opt("level", "l", "level", 1, 1, "<n>", "The level.")
+              .add_flag("verbose", "v", "verbose", "Prints more.")
+              .set_default("level", "1")
+              .add_requires("level", "verbose");
+
+        // The default shouldn't require anything
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.has_errors(), false);
+        let args = vec!(String::from("./test_exec"), String::from("-l"), String::from("2"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingDependency{ option: String::from("--level"), requires: String::from("--verbose") }));
+
+        // A default does satisfy a required group, but not at the cost of the other options
+        let mut parser = ArgParser::new();
+        parser.add_flag("json", "j", "json", "Outputs JSON.")
+              .add_opt("format", "f", "format", 1, 1, "<format>", "The output format.")
+              .set_default("format", "text")
+              .add_required_one_of(vec!(String::from("json"), String::from("format")));
+        assert_eq!(parser.parse(&vec!(String::from("./test_exec"))).has_errors(), false);
+        assert_eq!(parser.parse(&vec!(String::from("./test_exec"), String::from("-j"))).has_errors(), false);
+    }
+
+    #[test]
+    fn thread_safety() {
+        // The parser (including any validator) and the dict may be sent to and shared between threads
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ArgParser>();
+        assert_send_sync::<ArgDict>();
+    }
+
+    #[test]
+    fn auto_help_widths() {
+        // Create a parser that shows help automatically
+        let mut parser = ArgParser::new();
+        parser.add_pos("pos1", "pos1", "A test positional with a rather long description that will be wrapped once the line gets longer than eighty characters.")
+              .add_help()
+              .set_auto_help(true);
+
+        // Without a terminal width or set widths, the automatic help uses the default widths
+        let mut buffer: Vec<u8> = Vec::new();
+        parser.write_auto_help(&mut buffer, "./test_exec", None).unwrap();
+        let help = String::from_utf8(buffer).unwrap();
+        assert_eq!(help, parser.get_help("./test_exec", DEFAULT_INDENT_WIDTH, DEFAULT_LINE_WIDTH));
+        assert_eq!(help.contains(&format!("\n  <pos1>{}A test positional", " ".repeat(DEFAULT_INDENT_WIDTH - 8))), true);
+        assert_eq!(help.lines().all(|l| l.len() <= DEFAULT_LINE_WIDTH), true);
+
+        // With widths set, those are used instead
+        parser.set_help_widths(12, 200);
+        let mut buffer: Vec<u8> = Vec::new();
+        parser.write_auto_help(&mut buffer, "./test_exec", Some("40")).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), parser.get_help("./test_exec", 12, 200));
+    }
+
+    #[test]
+    fn value_error_indices() {
+        // Create a parser with an option that is restricted and split on commas
+        let mut parser = ArgParser::new();
+        parser.add_opt_choices("mode", "m", "mode", 1, 3, "<mode>", "A test option.", vec!(String::from("debug"), String::from("release")))
+              .set_value_delimiter("mode", ',', false)
+              .add_opt("verbose", "v", "verbose", 0, 0, "", "A test flag.");
+
+        // The index should point to the occurrence the value was given to, even after splitting
+        let args = vec!(String::from("./test_exec"), String::from("-m"), String::from("debug"), String::from("-v"), String::from("--mode"), String::from("release,fast"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::InvalidChoice{ option: String::from("--mode"), index: Some(4), value: String::from("fast"), choices: vec!(String::from("debug"), String::from("release")) }));
+        assert_eq!(dict.get_errors()[0].to_string(), "Invalid value 'fast' for '--mode' (argument 4): expected one of [debug, release].");
+
+        // Values that don't come from the command line have no index
+        parser.set_default("mode", "fast");
+        let args = vec!(String::from("./test_exec"));
+        let dict = parser.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::InvalidChoice{ option: String::from("--mode"), index: None, value: String::from("fast"), choices: vec!(String::from("debug"), String::from("release")) }));
+        assert_eq!(dict.get_errors()[0].to_string(), "Invalid value 'fast' for '--mode': expected one of [debug, release].");
+
+        // In a subcommand, the index should be relative to the full list
+        let mut parent = ArgParser::new();
+        parent.add_subcommand("sub", parser);
+        let args = vec!(String::from("./test_exec"), String::from("sub"), String::from("--mode"));
+        let dict = parent.parse(&args);
+        assert_eq!(dict.get_errors(), &vec!(ParseError::MissingValues{ option: String::from("--mode"), index: Some(2), expected: 1, got: 0 }));
+    }
+}
+
+
+
+
+
+/***** ERRORS *****/
+/// Defines the errors that may occur while parsing.
+/// 
+/// Any option names stored in the errors are written as the user would (e.g., '--opt1' or '-o'). Any indices are the position of the offending argument in the list given to `parse()` (after expanding response files), where the executable is index 0. Errors about the arguments as a whole (e.g., a missing or conflicting option, or an unreadable response file) have no index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError {
+    /// The given option is not known. The index is that of the option, where the executable is 0. May carry the longname (including dashes) of a known option that is close to it.
+    UnknownOption { option: String, index: usize, help_hint: bool, suggestion: std::option::Option<String> },
+    /// The given option does not accept values, but one was given immediately after it anyway. The index is that of the option, where the executable is 0.
+    UnexpectedValue { option: String, index: usize, value: String },
+    /// A value was given immediately after an option that accepts more than one value. The index is that of the option, where the executable is 0.
+    InlineValueUnsupported { option: String, index: usize, max: usize },
+    /// Not enough values were given to an option. The index is that of the (last) occurrence of the option that is short of values (where the executable is 0), or None if the values did not come from the command line (e.g., from an environment variable).
+    MissingValues { option: String, index: std::option::Option<usize>, expected: usize, got: usize },
+    /// Too many values were given to an option. The index is that of the (last) occurrence of the option with too many values (where the executable is 0), or None if the values did not come from the command line.
+    TooManyValues { option: String, index: std::option::Option<usize>, expected: usize, got: usize },
+    /// A value was given to an option or positional that is not one of its choices. The index is that of the positional or the option the value was given to (where the executable is 0), or None if the value did not come from the command line.
+    InvalidChoice { option: String, index: std::option::Option<usize>, value: String, choices: Vec<String> },
+    /// A required option was not given.
+    MissingRequired { option: String },
+    /// The given subcommand is not known.
+    UnknownSubcommand { subcommand: String, expected: Vec<String> },
+    /// A value given to a map option was not a 'key=value' pair. The index is that of the option the value was given to (where the executable is 0), or None if the value did not come from the command line.
+    InvalidKeyValue { option: String, index: std::option::Option<usize>, value: String },
+    /// A response file could not be read.
+    ResponseFile { file: String, reason: String },
+    /// More than one option of a mutually exclusive group was given.
+    MutuallyExclusive { options: Vec<String> },
+    /// An option was given without another option it requires.
+    MissingDependency { option: String, requires: String },
+    /// None of the options of a group of which exactly one is required was given.
+    MissingOneOf { options: Vec<String> },
+    /// An option that accepts a single value was given more than once. The index is that of its second occurrence, where the executable is 0.
+    DuplicateOption { option: String, index: std::option::Option<usize> },
+    /// A value was given to a positional that its validator rejected, or to an integer option that is not an integer. The index is that of the positional or the option the value was given to (where the executable is 0), or None if the value did not come from the command line.
+    InvalidValue { option: String, index: std::option::Option<usize>, value: String, reason: String },
+    /// A long option was given with a value, but without a name (e.g., '--=value'). The index is that of the argument, where the executable is 0.
+    MissingOptionName { index: usize, value: String },
+    /// More positionals were given than there are defined, while in strict mode. Unlike the other errors, the index is that of the value among the positionals given.
+    UnexpectedPositional { value: String, index: usize },
+    /// The custom validator set with `ArgParser::set_post_validator()` rejected the arguments.
+    Custom { message: String },
+    /// A value was given to an integer option that is outside of its range. The index is that of the option the value was given to (where the executable is 0), or None if the value did not come from the command line.
+    OutOfRange { option: String, index: std::option::Option<usize>, value: i64, min: i64, max: i64 },
+    /// The file given to an option that reads its value from a file could not be read. The index is that of the option the file was given to (where the executable is 0), or None if the file did not come from the command line.
+    UnreadableFile { option: String, index: std::option::Option<usize>, reason: String },
+}
+
+/// Defines the warnings that may occur while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseWarning {
+    /// More positionals were given than there are defined, so this one is skipped.
+    ExtraPositional { value: String, index: usize },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Use the default (English) messages
+        write!(f, "{}", Messages::default().format_warning(self))
+    }
+}
+
+impl ParseError {
+    /// Moves the argument index of this error (if it has any) by the given offset, e.g., to make the index of an error of a subcommand relative to the parent's arguments.
+    /// 
+    /// **Arguments**
+    ///  * `offset`: The number to add to the index.
+    /// 
+    /// **Returns**  
+    /// The same error, with the new index.
+    fn offset_index(mut self, offset: usize) -> ParseError {
+        use ParseError::*;
+        match &mut self {
+            UnknownOption{ index, .. }               |
+            UnexpectedValue{ index, .. }             |
+            MissingOptionName{ index, .. }           |
+            InlineValueUnsupported{ index, .. }      |
+            MissingValues{ index: Some(index), .. }  |
+            TooManyValues{ index: Some(index), .. }  |
+            InvalidChoice{ index: Some(index), .. }  |
+            InvalidKeyValue{ index: Some(index), .. } |
+            DuplicateOption{ index: Some(index), .. } |
+            InvalidValue{ index: Some(index), .. }   |
+            OutOfRange{ index: Some(index), .. }     |
+            UnreadableFile{ index: Some(index), .. } => { *index += offset; },
+            _                                        => {},
+        }
+        return self;
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Use the default (English) messages
+        write!(f, "{}", Messages::default().format(self))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Defines all errors that occurred during a single parse, as returned by `ArgParser::try_parse()`.
+/// 
+/// Can be used as a `Box<dyn std::error::Error>` (e.g., with `?` in a `main()` that returns one), and derefs to the list of errors for inspecting them one-by-one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgErrors(pub Vec<ParseError>);
+
+impl std::ops::Deref for ArgErrors {
+    type Target = Vec<ParseError>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl fmt::Display for ArgErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Write each error on its own line
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 { writeln!(f)?; }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ArgErrors {
+    fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
+        // The first error is considered the cause
+        self.0.first().map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+
+
+
+
+/***** MESSAGES *****/
+/// Defines the templates for the messages shown to the user when parsing, so they may be translated or otherwise changed.
+/// 
+/// Each template may contain numbered placeholders (e.g., '{0}' or '{1}'), which are replaced with the values listed in the field's documentation. The defaults are in English.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Messages {
+    /// An unknown option was given. Placeholders: the option, the help hint (see `help_hint`; empty if help is not used), the argument index.
+    pub unknown_option            : String,
+    /// An unknown option was given, but it is close to a known one. Placeholders: the option, the suggested option, the argument index.
+    pub unknown_option_suggestion : String,
+    /// Appended to `unknown_option` if help is used. No placeholders.
+    pub help_hint                 : String,
+    /// Filled into the templates of errors about values if those were given on the command line (empty otherwise). Placeholders: the argument index.
+    pub argument_index            : String,
+    /// An option that doesn't accept values was given one. Placeholders: the option, the value, the argument index.
+    pub unexpected_value          : String,
+    /// A value was given immediately after an option that accepts more than one. Placeholders: the option, its maximum number of values, the argument index.
+    pub inline_value_unsupported  : String,
+    /// An option was given too few values. Placeholders: the option, the minimum, the number given, the argument index (see `argument_index`).
+    pub missing_values            : String,
+    /// An option was given too many values. Placeholders: the option, the maximum, the number given, the argument index (see `argument_index`).
+    pub too_many_values           : String,
+    /// An option was given a value that is not one of its choices. Placeholders: the value, the option, the comma-separated choices, the argument index (see `argument_index`).
+    pub invalid_choice            : String,
+    /// A required option was not given. Placeholders: the option.
+    pub missing_required          : String,
+    /// An unknown subcommand was given. Placeholders: the subcommand, the comma-separated known subcommands.
+    pub unknown_subcommand        : String,
+    /// A map option was given a value that is not a 'key=value' pair. Placeholders: the option, the value, the argument index (see `argument_index`).
+    pub invalid_key_value         : String,
+    /// A response file could not be read. Placeholders: the file, the reason.
+    pub response_file             : String,
+    /// More than one option of a mutually exclusive group was given. Placeholders: all but the last option (quoted and comma-separated), the last option (quoted).
+    pub mutually_exclusive        : String,
+    /// An option was given without an option it requires. Placeholders: the option, the required option.
+    pub missing_dependency        : String,
+    /// None of a group of options of which one is required was given. Placeholders: all options (quoted and comma-separated).
+    pub missing_one_of            : String,
+    /// A single-value option was given more than once. Placeholders: the option, the argument index (see `argument_index`).
+    pub duplicate_option          : String,
+    /// A value was rejected by a validator or is not an integer. Placeholders: the value, the positional or option, the reason, the argument index (see `argument_index`).
+    pub invalid_value             : String,
+    /// A long option was given with a value but without a name. Placeholders: the value, the argument index.
+    pub missing_option_name       : String,
+    /// Too many positionals were given in strict mode. Placeholders: the value, its index.
+    pub unexpected_positional     : String,
+    /// The warning given when too many positionals were given. Placeholders: the value, its index.
+    pub skipped_positional        : String,
+    /// The custom validator rejected the arguments. Placeholders: the message it returned.
+    pub custom                    : String,
+    /// A value was outside of the range of an integer option. Placeholders: the value, the option, the minimum, the maximum, the argument index (see `argument_index`).
+    pub out_of_range              : String,
+    /// The file given to an option that reads its value from it could not be read. Placeholders: the option, the reason, the argument index (see `argument_index`).
+    pub unreadable_file           : String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Messages {
+            unknown_option            : String::from("Unknown option '{0}' (argument {2}){1}"),
+            unknown_option_suggestion : String::from("Unknown option '{0}' (argument {2}); did you mean '{1}'?"),
+            help_hint                 : String::from("; use '--help' to see an overview of accepted options."),
+            argument_index            : String::from(" (argument {0})"),
+            unexpected_value          : String::from("Option '{0}' (argument {2}) cannot accept values (is passed '{1}')."),
+            inline_value_unsupported  : String::from("Passing a value immediately after an option is only supported for options with at most 1 value ('{0}' has at most {1}; argument {2})."),
+            missing_values            : String::from("Not enough values for '{0}'{3}: expected at least {1}, got {2}."),
+            too_many_values           : String::from("Too many values for '{0}'{3}: expected at most {1}, got {2}."),
+            invalid_choice            : String::from("Invalid value '{0}' for '{1}'{3}: expected one of [{2}]."),
+            missing_required          : String::from("Missing required option '{0}'."),
+            unknown_subcommand        : String::from("Unknown subcommand '{0}'; expected one of [{1}]."),
+            invalid_key_value         : String::from("Expected key=value for '{0}'{2}, got '{1}'."),
+            response_file             : String::from("Could not read argument file '{0}': {1}"),
+            mutually_exclusive        : String::from("Options {0} and {1} are mutually exclusive."),
+            missing_dependency        : String::from("Option '{0}' requires '{1}' to also be specified."),
+            missing_one_of            : String::from("One of {0} is required."),
+            duplicate_option          : String::from("Option '{0}'{1} specified more than once."),
+            invalid_value             : String::from("Invalid value '{0}' for '{1}'{3}: {2}"),
+            missing_option_name       : String::from("Missing option name before '=' (argument {1})."),
+            unexpected_positional     : String::from("Unexpected positional '{0}' (index {1})."),
+            skipped_positional        : String::from("Skipping positional '{0}' (index {1})..."),
+            custom                    : String::from("{0}"),
+            out_of_range              : String::from("Value {0} for '{1}'{4} out of range [{2}, {3}]."),
+            unreadable_file           : String::from("Could not read file for '{0}'{2}: {1}"),
+        }
+    }
+}
+
+impl Messages {
+    /// Replaces the numbered placeholders in the given template with the given values.
+    /// 
+    /// Placeholders without a matching value are left as-is.
+    /// 
+    /// **Arguments**
+    ///  * `template`: The template to fill in.
+    ///  * `values`: The values to fill in, where the first replaces '{0}', the second '{1}', etc.
+    /// 
+    /// **Returns**  
+    /// The filled-in template.
+    pub fn fill(template: &str, values: &[&str]) -> String {
+        let mut result: String = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            // Write everything up to the brace
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            // See if it's a placeholder we know
+            if let Some(end) = rest.find('}') {
+                if let Ok(i) = rest[1..end].parse::<usize>() {
+                    if i < values.len() {
+                        result.push_str(values[i]);
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                }
+            }
+
+            // Otherwise, just write the brace
+            result.push('{');
+            rest = &rest[1..];
+        }
+        result.push_str(rest);
+
+        // Done
+        return result;
+    }
+
+    /// Formats the given warning using these templates.
+    /// 
+    /// **Arguments**
+    ///  * `warning`: The warning to format.
+    /// 
+    /// **Returns**  
+    /// The message describing the warning.
+    pub fn format_warning(&self, warning: &ParseWarning) -> String {
+        use ParseWarning::*;
+        match warning {
+            ExtraPositional{ value, index } => Messages::fill(&self.skipped_positional, &[value, &index.to_string()]),
+        }
+    }
+
+    /// Formats the given error using these templates.
+    /// 
+    /// **Arguments**
+    ///  * `error`: The error to format.
+    /// 
+    /// **Returns**  
+    /// The message describing the error.
+    pub fn format(&self, error: &ParseError) -> String {
+        use ParseError::*;
+        let argument_index = |index: &std::option::Option<usize>| index.map(|index| Messages::fill(&self.argument_index, &[&index.to_string()])).unwrap_or_default();
+        match error {
+            UnknownOption{ option, index, help_hint, suggestion } => match suggestion {
+                Some(suggestion) => Messages::fill(&self.unknown_option_suggestion, &[option, suggestion, &index.to_string()]),
+                None             => Messages::fill(&self.unknown_option, &[option, if *help_hint { &self.help_hint } else { "" }, &index.to_string()]),
+            },
+            UnexpectedValue{ option, index, value }      => Messages::fill(&self.unexpected_value, &[option, value, &index.to_string()]),
+            InlineValueUnsupported{ option, index, max } => Messages::fill(&self.inline_value_unsupported, &[option, &max.to_string(), &index.to_string()]),
+            MissingValues{ option, index, expected, got } => Messages::fill(&self.missing_values, &[option, &expected.to_string(), &got.to_string(), &argument_index(index)]),
+            TooManyValues{ option, index, expected, got } => Messages::fill(&self.too_many_values, &[option, &expected.to_string(), &got.to_string(), &argument_index(index)]),
+            InvalidChoice{ option, index, value, choices } => Messages::fill(&self.invalid_choice, &[value, option, &choices.join(", "), &argument_index(index)]),
+            MissingRequired{ option }                    => Messages::fill(&self.missing_required, &[option]),
+            UnknownSubcommand{ subcommand, expected }    => Messages::fill(&self.unknown_subcommand, &[subcommand, &expected.join(", ")]),
+            InvalidKeyValue{ option, index, value }      => Messages::fill(&self.invalid_key_value, &[option, value, &argument_index(index)]),
+            ResponseFile{ file, reason }                 => Messages::fill(&self.response_file, &[file, reason]),
+            MutuallyExclusive{ options }                 => {
+                let names: Vec<String> = options.iter().map(|o| format!("'{}'", o)).collect();
+                Messages::fill(&self.mutually_exclusive, &[&names[..names.len() - 1].join(", "), &names[names.len() - 1]])
+            },
+            MissingDependency{ option, requires }        => Messages::fill(&self.missing_dependency, &[option, requires]),
+            MissingOneOf{ options }                      => {
+                let names: Vec<String> = options.iter().map(|o| format!("'{}'", o)).collect();
+                Messages::fill(&self.missing_one_of, &[&names.join(", ")])
+            },
+            DuplicateOption{ option, index }             => Messages::fill(&self.duplicate_option, &[option, &argument_index(index)]),
+            InvalidValue{ option, index, value, reason } => Messages::fill(&self.invalid_value, &[value, option, reason, &argument_index(index)]),
+            MissingOptionName{ index, value }            => Messages::fill(&self.missing_option_name, &[value, &index.to_string()]),
+            UnexpectedPositional{ value, index }         => Messages::fill(&self.unexpected_positional, &[value, &index.to_string()]),
+            Custom{ message }                            => Messages::fill(&self.custom, &[message]),
+            OutOfRange{ option, index, value, min, max } => Messages::fill(&self.out_of_range, &[&value.to_string(), option, &min.to_string(), &max.to_string(), &argument_index(index)]),
+            UnreadableFile{ option, index, reason }      => Messages::fill(&self.unreadable_file, &[option, reason, &argument_index(index)]),
+        }
+    }
+}
+
+
+
+
+
+/***** TRAITS *****/
+/// Defines types that can be constructed from the results of parsing, such as a program's own configuration struct.
+/// 
+/// Implementations typically use the typed getters on the ArgDict (e.g., `get_opt_as()` or `require_opt_as()`) to fill in their fields.
+pub trait FromArgDict: Sized {
+    /// Constructs a new instance of this type from the given ArgDict.
+    /// 
+    /// **Arguments**
+    ///  * `dict`: The ArgDict with the parsed arguments.
+    /// 
+    /// **Returns**  
+    /// The new instance on success, or a String describing what went wrong otherwise.
+    fn from_dict(dict: &ArgDict) -> Result<Self, String>;
+}
+
+
+
+
+
+/***** HELPER STRUCTS *****/
+/// Intermediate representation for a Positional.
+#[derive(Clone, Debug)]
+struct Positional {
+    /// The uid for this positional.
+    uid         : String,
+    /// The index of this positional.
+    index       : usize,
+    /// The human-readable name for this positional. Used in the usage/help string.
+    name        : String,
+    /// The description for this positional.
+    description : String,
+    /// Whether or not this positional collects all remaining values.
+    multi       : bool,
+    /// Whether or not this positional may be left out, even if positionals after it are given.
+    optional    : bool,
+    /// The values this positional is restricted to. Will be empty if any value is allowed.
+    choices     : Vec<String>,
+    /// The function that checks if a value is valid for this positional, if any. Returns a description of what's wrong if it isn't.
+    validator   : std::option::Option<fn(&str) -> Result<(), String>>,
+    /// The name shown in the usage/help string instead of the name, if any.
+    metavar     : std::option::Option<String>,
+    /// Whether or not this positional is left out of the usage/help string.
+    hidden      : bool,
+}
+
+/// Intermediate representation for an Option.
+#[derive(Clone, Debug)]
+struct Option {
+    /// The uid for this option.
+    uid               : String,
+    /// The shortname for this option. Will be the empty char (`\0`) if unused.
+    shortname         : String,
+    /// The longname for this option.
+    longname          : String,
+    /// The minimum number of values for this option.
+    min_n_values      : usize,
+    /// The maximum number of values for this option.
+    max_n_values      : usize,
+    /// The description of the parameters for this option.
+    param_description : String,
+    /// The description for this option.
+    description       : String,
+    /// The name of the environment variable to read the values from if the option isn't given. Will be empty if unused.
+    env_var           : String,
+    /// Whether or not this option is a flag that may be negated with '--no-<longname>'.
+    negatable         : bool,
+    /// Whether or not this option must always be given.
+    required          : bool,
+    /// The values this option is restricted to. Will be empty if any value is allowed.
+    choices           : Vec<String>,
+    /// Whether or not the number of occurrences of this option is counted.
+    counted           : bool,
+    /// Whether or not this option collects 'key=value' pairs, one per occurrence.
+    map               : bool,
+    /// Whether or not this option may be given multiple times, with its value bounds applying to each occurrence.
+    repeatable        : bool,
+    /// The name of the group this option is listed under in the help string. Will be empty if it isn't in a group.
+    group             : String,
+    /// Any alternative longnames for this option.
+    aliases           : Vec<String>,
+    /// Any alternative shortnames for this option.
+    short_aliases     : Vec<String>,
+    /// The character on which to split each value of this option into multiple values, if any.
+    value_delimiter   : std::option::Option<char>,
+    /// Whether or not to keep empty values that result from splitting on the value delimiter.
+    keep_empty_values : bool,
+    /// The argument that ends the values of this option, if any.
+    value_terminator  : std::option::Option<String>,
+    /// Whether or not this option takes values that look like options (e.g., '--message --verbose').
+    allow_dash_values : bool,
+    /// The (inclusive) range of integers the values of this option must be in, if restricted.
+    int_range         : std::option::Option<(i64, i64)>,
+    /// Whether or not this option must be given if it has a positive `min_n_values`.
+    require_if_min    : bool,
+    /// Whether or not the value of this option is a file to read the actual value from, and if so, whether to trim its contents.
+    from_file         : std::option::Option<bool>,
+    /// Whether or not this option is left out of the usage/help string and the completions.
+    hidden            : bool,
+    /// Whether or not this option takes all arguments after it verbatim.
+    rest              : bool,
+    /// The value used if this option is given neither on the command line nor in its environment variable, if any.
+    default_value     : std::option::Option<String>,
+}
+
+/// Wraps the custom validator that is run after parsing, so that the ArgParser can still be cloned, debug-printed and shared between threads.
+#[derive(Clone)]
+struct PostValidator(std::sync::Arc<dyn Fn(&ArgDict) -> Vec<String> + Send + Sync>);
+
+impl fmt::Debug for PostValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PostValidator")
+    }
+}
+
+
+
+
+
+/***** INFO STRUCTS *****/
+/// Read-only view of a registered positional, as returned by `ArgParser::pos_defs()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionalInfo<'a> {
+    /// The uid of the positional.
+    pub uid         : &'a str,
+    /// The index of the positional.
+    pub index       : usize,
+    /// The name of the positional, as shown in the help string.
+    pub name        : &'a str,
+    /// The description of the positional.
+    pub description : &'a str,
+    /// Whether or not the positional collects all remaining values.
+    pub multi       : bool,
+    /// Whether or not the positional may be left out.
+    pub optional    : bool,
+    /// Whether or not the positional is hidden from the help string.
+    pub hidden      : bool,
+}
+
+/// Read-only view of a registered option, as returned by `ArgParser::opt_defs()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionInfo<'a> {
+    /// The uid of the option.
+    pub uid               : &'a str,
+    /// The shortname of the option. Will be empty if unused.
+    pub shortname         : &'a str,
+    /// The longname of the option.
+    pub longname          : &'a str,
+    /// Any alternative longnames of the option.
+    pub aliases           : &'a [String],
+    /// Any alternative shortnames of the option.
+    pub short_aliases     : &'a [String],
+    /// The minimum number of values of the option.
+    pub min_n_values      : usize,
+    /// The maximum number of values of the option.
+    pub max_n_values      : usize,
+    /// The description of the parameters of the option.
+    pub param_description : &'a str,
+    /// The description of the option.
+    pub description       : &'a str,
+    /// Whether or not the option must always be given.
+    pub required          : bool,
+    /// Whether or not the option is hidden from the help string and the completions.
+    pub hidden            : bool,
+}
+
+
+
+
+
+/***** WORDITERATOR HELPER CLASS *****/
+/// Helper iterator over a string, that returns word-by-word instead of char-by-char.
+/// 
+/// Uses the graphene method to have intuitive characters. By default, words are separated by a space, newline (`\n`), carriage return (`\r`) or tab (`\t`), but other separators may be given with `WordIterator::with_separators()`. The last word is followed by a null-character (`\0`) to mark the end of the string, after which the iterator ends.
+/// 
+/// **Template parameters**
+///  * `'a`: The lifetime parameter for the WorldIterator, which should be itself.
+pub struct WordIterator<'a> {
+    /// The string we iterate over
+    s    : OpString<'a>,
+    /// The current position in the string
+    i    : usize,
+    /// The graphemes that separate words
+    seps : Vec<String>,
+}
+
+impl<'a> WordIterator<'a> {
+    /// Constructor for the WordIterator class
+    /// 
+    /// **Arguments**
+    ///  * `s`: The string to iterate over.
+    pub fn new(s: &'a str) -> WordIterator<'a> {
+        // Use the default separators
+        return WordIterator::with_separators(s, DEFAULT_WORD_SEPARATORS);
+    }
+
+    /// Constructor for the WordIterator class that uses the given separators instead of the default ones.
+    /// 
+    /// **Arguments**
+    ///  * `s`: The string to iterate over.
+    ///  * `seps`: The graphemes that separate words (e.g., `&[" ", "\n", "/"]`). The end of the string (`\0`) is always a separator.
+    pub fn with_separators(s: &'a str, seps: &[&str]) -> WordIterator<'a> {
+        // Return the new WordIterator
+        return WordIterator {
+            s    : OpString::new(s),
+            i    : 0,
+            seps : seps.iter().map(|sep| String::from(*sep)).collect(),
+        };
+    }
+}
+
+impl<'a> Iterator for WordIterator<'a> {
+    /// The type of each item returned by the iterator
+    type Item = (&'a str, &'a str);
+
+    /// Gets the next word/separator pair in the internal string.  
+    /// A separator is what splits words, and can either be one of the separators (by default, any whitespace: space, newline (`\n`), carriage return (`\r`) or tab (`\t`)) or a null-character (`\0`) in case of end-of-string.
+    /// 
+    /// **Returns**  
+    /// An Option with, if we didn't reach the end yet, a tuple bearing the word (possibly empty in case of two consecutive separators) and the separator following after it. Is None once the end-of-string separator has been returned.
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        // Stop if we already returned the end of the string
+        if self.i > self.s.len() { return None; }
+
+        // Continue with iterating where we were
+        let start_i = self.i;
+        loop {
+            // Get the next char
+            let c: &str;
+            if self.i < self.s.len() { c = self.s[self.i]; }
+            else { c = "\0"; }
+
+            // See if it's a separator
+            if c.eq("\0") || self.seps.iter().any(|sep| sep.eq(c)) {
+                // It is; return the result + the separator
+                let start_j = self.s.translate_opstr(start_i);
+                let end_j   = self.s.translate_opstr(self.i);
+                self.i += 1;
+                return Some((&self.s.parent()[start_j..end_j], c));
+            }
+
+            // Otherwise, move the internal i (which counts graphemes, not bytes)
+            self.i += 1;
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY FUNCTIONS *****/
+/// Line-wraps the given text in the same way as the descriptions in the help string are.
+/// 
+/// Newlines and tabs in the text are respected, and words that are too long for a single line are broken up.
+/// 
+/// **Arguments**
+///  * `text`: The text to wrap.
+///  * `indent_width`: The number of spaces before each line, including the first.
+///  * `line_width`: The total line width of each line. Must be larger than `indent_width`.
+/// 
+/// **Returns**  
+/// The wrapped text, without a trailing newline.
+pub fn wrap_text(text: &str, indent_width: usize, line_width: usize) -> String {
+    // Start with the indent for the first line
+    let mut result: String = ArgParser::generate_spaces(indent_width);
+    let mut x = indent_width;
+
+    // Write the text itself
+    ArgParser::print_description(&mut result, &mut x, text, indent_width, line_width, DEFAULT_TAB_WIDTH, DEFAULT_WORD_SEPARATORS);
+    return result;
+}
+
+
+
+
+
+/***** ARGPARSER CLASS *****/
+/// Defines how the names of positionals are enclosed in the usage and help strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BracketStyle {
+    /// The names are enclosed in angle brackets, e.g., `<file>`. This is the default.
+    Angle,
+    /// The names are enclosed in square brackets, e.g., '[file]'.
+    Square,
+    /// The names are shown as-is, e.g., 'FILE'.
+    Plain,
+}
+
+/// Defines a single instance for arguments.
+#[derive(Clone, Debug)]
+pub struct ArgParser {
+    /// Stores the defined positionals in the parser.
+    positionals       : Vec<Positional>,
+    /// Stores the defined options in the parser.
+    options           : Vec<Option>,
+    /// Stores the defined subcommands in the parser, each with its own nested parser.
+    subcommands       : Vec<(String, ArgParser)>,
+    /// Stores the names of the groups options are listed under in the help string, in order.
+    opt_groups        : Vec<String>,
+    /// Stores groups of option uids of which at most one may be given.
+    mutex_groups      : Vec<Vec<String>>,
+    /// Stores groups of option uids of which exactly one must be given.
+    required_groups   : Vec<Vec<String>>,
+    /// Stores pairs of option uids where, if the first is given, the second must be given too.
+    requirements      : Vec<(String, String)>,
+
+    /// Determines whether or not the double-dash argument is used
+    use_double_dash   : bool,
+    /// Determines whether or not the help is given
+    use_help          : bool,
+    /// Determines whether or not the help is printed to stdout by parse() if given
+    auto_help         : bool,
+    /// Determines whether or not arguments starting with '@' are read as response files
+    use_resp_files    : bool,
+    /// Determines whether or not option names are matched case-insensitively
+    case_insensitive  : bool,
+    /// Determines whether or not the aliases of options are shown in the help string
+    show_aliases      : bool,
+    /// Determines whether or not all arguments after the first positional are collected verbatim as trailing arguments
+    stop_at_first_pos : bool,
+    /// Determines whether or not superfluous positionals are errors instead of warnings
+    strict            : bool,
+    /// The characters that start an option (doubled for longnames)
+    opt_prefixes      : Vec<char>,
+    /// The indent and line widths of the help string, if overridden
+    help_widths       : std::option::Option<(usize, usize)>,
+    /// The distance between tab stops in descriptions in the help string
+    tab_width         : usize,
+    /// Determines whether or not options that are short of their minimum number of values take positionals given after them
+    greedy_values     : bool,
+    /// The templates for the errors and warnings shown to the user
+    messages          : Messages,
+    /// The placeholder for the options in the usage string, or 'none' to leave it out
+    usage_opts_label  : std::option::Option<String>,
+    /// Determines whether or not giving help clears any errors and warnings
+    help_supp_errors  : bool,
+    /// Determines whether or not the indent width of the help string is computed from the names of the positionals and options
+    auto_indent       : bool,
+    /// Determines whether or not the defaults and choices are appended to the descriptions in the help string
+    annotate_help     : bool,
+    /// Determines whether or not the help string is written without its leading and trailing blank lines
+    help_trim         : bool,
+    /// Determines whether or not unknown options are collected in the dict instead of resulting in errors
+    collect_unknown   : bool,
+    /// The graphemes that separate words in descriptions in the help string
+    word_separators   : Vec<String>,
+    /// The brackets around the names of positionals in the usage and help strings
+    pos_brackets      : BracketStyle,
+    /// The number of superfluous positionals that are skipped with a warning before they become errors, if limited
+    max_extra_pos     : std::option::Option<usize>,
+    /// The custom validator that is run after the built-in validation, if any
+    post_validator    : std::option::Option<PostValidator>,
+}
+
+/// Defines the ArgParser's methods
+impl ArgParser {
+    /// Constructor for the ArgParser, which is public.
+    pub fn new() -> ArgParser {
+        ArgParser {
+            positionals       : Vec::new(),
+            options           : Vec::new(),
+            subcommands       : Vec::new(),
+            opt_groups        : Vec::new(),
+            mutex_groups      : Vec::new(),
+            required_groups   : Vec::new(),
+            requirements      : Vec::new(),
+            use_double_dash   : false,
+            use_help          : false,
+            auto_help         : false,
+            use_resp_files    : true,
+            case_insensitive  : false,
+            show_aliases      : false,
+            stop_at_first_pos : false,
+            strict            : false,
+            opt_prefixes      : vec!('-'),
+            help_widths       : None,
+            tab_width         : DEFAULT_TAB_WIDTH,
+            greedy_values     : false,
+            messages          : Messages::default(),
+            usage_opts_label  : Some(String::from("[options]")),
+            help_supp_errors  : true,
+            auto_indent       : false,
+            annotate_help     : false,
+            help_trim         : false,
+            collect_unknown   : false,
+            word_separators   : DEFAULT_WORD_SEPARATORS.iter().map(|sep| String::from(*sep)).collect(),
+            pos_brackets      : BracketStyle::Angle,
+            max_extra_pos     : None,
+            post_validator    : None,
+        }
+    }
+
+    
+
+    /// Helper function that parses at most max_n values from the given list of arguments.
+    /// 
+    /// **Arguments**
+    ///  * `args`: The list of arguments to parse from.
+    ///  * `i`: Reference to the current position within args. Will be increment as we parse, and is left at the last-parsed argument.
+    ///  * `max_n`: The maximum number of arguments to parse.
+    ///  * `terminator`: If given, the argument that ends the values. It is consumed, but not added to the values.
+    ///  * `allow_dash`: Whether or not arguments that look like options are taken as values too (except for the double dash, if used).
+    ///  * `double_dash`: The index of the double dash, if any was encountered. Options are only parsed as long as this is None, and it is set if we have use_double_dash set and we encounter it.
+    ///  * `owner`: The name of the option the values are for (including dashes), used in the trace.
+    ///  * `trace`: The list of arguments paired with how they were classified, to which the parsed values are added.
+    /// **Returns**  
+    /// The popped arguments, of which there will be at most max_n.
+    fn parse_values(&self, args: &Vec<String>, i: &mut usize, max_n: usize, terminator: std::option::Option<&str>, allow_dash: bool, double_dash: &mut std::option::Option<usize>, owner: &str, trace: &mut Vec<(String, String)>) -> Vec<String> {
+        // Increment i to skip the option itself
+        *i += 1;
+
+        // Try to pop (a double dash in between does not count as a value)
+        let mut result: Vec<String> = Vec::new();
+        while *i < args.len() {
+            // Get the argument
+            let arg = &args[*i];
+            let sarg = OpString::new(arg);
+
+            // Stop at the terminator, if any, and consume it
+            if terminator.map(|t| arg.eq(t)).unwrap_or(false) {
+                trace.push((arg.clone(), String::from("terminator")));
+                *i += 1;
+                break;
+            }
+
+            // Stop if we have enough values
+            if result.len() >= max_n { break; }
+
+            // If it's an option (but not a lone dash, a bare double dash that isn't a separator or a negative number), stop
+            if double_dash.is_none() && sarg.len() > 1 && self.is_opt_prefix(sarg[0]) && (self.use_double_dash || !(sarg.len() == 2 && sarg[1].eq(sarg[0]))) && !self.is_negative_number(arg) {
+                // Make sure its not the other one
+                if self.use_double_dash && sarg.len() == 2 && sarg[1].eq(sarg[0]) {
+                    trace.push((arg.clone(), String::from("separator")));
+                    *double_dash = Some(*i);
+                    *i += 1;
+                    continue;
+                }
+
+                // Stop, unless the option takes those as values anyway
+                if !allow_dash { break; }
+            }
+
+            // Otherwise, add to the result (which may be an empty value)
+            result.push(arg.clone());
+            trace.push((arg.clone(), format!("value-of:{}", owner)));
+
+            // Increment i
+            *i += 1;
+        }
+
+        // i is now at the first unparseable thing; fix this for the main increment
+        *i -= 1;
+
+        // Return the result struct
+        return result;
+    }
+
+    /// Helper function that collects the values given separately from an unknown option, i.e., all arguments following it up to the next option or double dash.
+    /// 
+    /// **Arguments**
+    ///  * `args`: The list of arguments to parse.
+    ///  * `i`: The index of the unknown option. Will be left at the last value collected.
+    ///  * `owner`: The unknown option, used to trace the values.
+    ///  * `trace`: The trace to record the values in.
+    /// 
+    /// **Returns**  
+    /// The values, as given.
+    fn parse_unknown_values(&self, args: &Vec<String>, i: &mut usize, owner: &str, trace: &mut Vec<(String, String)>) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        while *i + 1 < args.len() {
+            // Stop at anything that looks like an option (including the double dash, if registered)
+            let arg = &args[*i + 1];
+            let sarg = OpString::new(arg);
+            if sarg.len() > 1 && self.is_opt_prefix(sarg[0]) && (self.use_double_dash || !(sarg.len() == 2 && sarg[1].eq(sarg[0]))) && !self.is_negative_number(arg) { break; }
+
+            // Otherwise, take it as a value
+            result.push(arg.clone());
+            trace.push((arg.clone(), format!("value-of:{}", owner)));
+            *i += 1;
+        }
+
+        // Done
+        return result;
+    }
+
+    /// Helper function that replaces any argument starting with '@' with the whitespace-separated contents of the file it refers to.
+    /// 
+    /// **Arguments**
+    ///  * `args`: The list of arguments to expand. The first argument (the executable) is never expanded.
+    ///  * `errors`: The list of errors to push to if a file could not be read.
+    /// 
+    /// **Returns**  
+    /// The new list of arguments, with the files spliced in.
+    fn expand_resp_files(args: &Vec<String>, errors: &mut Vec<ParseError>) -> Vec<String> {
+        let mut result: Vec<String> = Vec::with_capacity(args.len());
+        for (i, arg) in args.iter().enumerate() {
+            // Only do those that start with an '@'
+            if i == 0 || !arg.starts_with('@') {
+                result.push(arg.clone());
+                continue;
+            }
+
+            // Try to read the file and splice its contents in place
+            match std::fs::read_to_string(&arg[1..]) {
+                Ok(contents) => {
+                    for value in contents.split_whitespace() {
+                        result.push(String::from(value));
+                    }
+                },
+                Err(reason) => {
+                    errors.push(ParseError::ResponseFile{ file: arg.clone(), reason: reason.to_string() });
+                }
+            }
+        }
+
+        // Done
+        return result;
+    }
+
+    /// Helper function that compares an option name with (part of) a given argument, honouring case-insensitivity if set.
+    /// 
+    /// **Arguments**
+    ///  * `name`: The name of the option as it was registered.
+    ///  * `given`: The name as given by the user.
+    /// 
+    /// **Returns**  
+    /// Whether or not the two names match.
+    fn names_eq(&self, name: &str, given: &str) -> bool {
+        if self.case_insensitive {
+            name.to_lowercase() == given.to_lowercase()
+        } else {
+            name.eq(given)
+        }
+    }
+
+    /// Helper function that assigns the given positional values to the registered positionals.
+    /// 
+    /// Optional positionals are only filled (from left to right) if there are more values than there are required positionals. Any values left over are either collected by the variadic positional (if any) or skipped with a warning. The variadic positional leaves enough values for the positionals after it, so e.g. with '<files...> <out>', the last value is always given to 'out'.
+    /// 
+    /// **Arguments**
+    ///  * `values`: The positional values given by the user, in order.
+    ///  * `result`: The ArgDict to store the positionals (and any warnings) in.
+    /// 
+    /// **Returns**  
+    /// For each value, the uid of the positional it was assigned to, or None if it was skipped.
+    fn assign_positionals(&self, values: &Vec<String>, result: &mut ArgDict) -> Vec<std::option::Option<String>> {
+        // Determine how many optional positionals we can fill
+        let n_required = self.positionals.iter().filter(|p| !p.optional && !p.multi).count();
+        let mut n_optional = if values.len() > n_required { values.len() - n_required } else { 0 };
+
+        // Go through the positionals to assign them
+        let mut assigned: Vec<std::option::Option<String>> = vec!(None; values.len());
+        let mut value_i = 0;
+        for pos in self.positionals.iter() {
+            if value_i >= values.len() { break; }
+
+            // Skip optional ones if there aren't enough values
+            if pos.optional {
+                if n_optional == 0 { continue; }
+                n_optional -= 1;
+            }
+
+            // Assign the value(s)
+            if pos.multi {
+                // Take all values except the ones needed for the (required) positionals after it
+                let n_after = self.positionals.len() - 1 - pos.index;
+                let end = if values.len() > value_i + n_after { values.len() - n_after } else { value_i };
+                if end > value_i {
+                    result.multi_positionals.insert(pos.uid.clone(), (pos.index, values[value_i..end].to_vec()));
+                    result.positionals.insert(pos.uid.clone(), (pos.index, values[value_i].clone()));
+                    for a in assigned[value_i..end].iter_mut() { *a = Some(pos.uid.clone()); }
+                }
+                value_i = end;
+            } else {
+                result.positionals.insert(pos.uid.clone(), (pos.index, values[value_i].clone()));
+                assigned[value_i] = Some(pos.uid.clone());
+                value_i += 1;
+            }
+        }
+
+        // Warn about any values left (or error, in strict mode or if there are too many)
+        for (i, value) in values.iter().enumerate().skip(value_i) {
+            if self.strict || self.max_extra_pos.map(|n| result.n_skipped_pos >= n).unwrap_or(false) {
+                result.errors.push(ParseError::UnexpectedPositional{ value: value.clone(), index: i });
+            } else {
+                result.warnings.push(ParseWarning::ExtraPositional{ value: value.clone(), index: i });
+            }
+            result.n_skipped_pos += 1;
+        }
+
+        // Done
+        return assigned;
+    }
+
+    /// Helper function that splits a value from outside the command line (i.e., an environment variable or a default) into values for the given option.
+    /// 
+    /// **Arguments**
+    ///  * `opt`: The option to split the value for.
+    ///  * `value`: The value to split.
+    /// 
+    /// **Returns**  
+    /// No values if the option accepts none, the value as a whole if it accepts at most one, or the value split on whitespace otherwise.
+    fn split_fallback_value(opt: &Option, value: &str) -> Vec<String> {
+        if opt.max_n_values == 0 {
+            return Vec::new();
+        } else if opt.max_n_values == 1 {
+            return vec!(String::from(value));
+        } else {
+            return value.split_whitespace().map(|v| String::from(v)).collect();
+        }
+    }
+
+    /// Helper function that checks if the given character is one of the prefixes that start an option.
+    /// 
+    /// **Arguments**
+    ///  * `c`: The character to check, as a grapheme.
+    /// 
+    /// **Returns**  
+    /// Whether or not the character is an option prefix.
+    #[inline]
+    fn is_opt_prefix(&self, c: &str) -> bool {
+        self.opt_prefixes.iter().any(|p| c.len() == p.len_utf8() && c.starts_with(*p))
+    }
+
+    /// Helper function that checks if the given argument looks like a negative number (e.g., '-5' or '-0.5') rather than an option.
+    /// 
+    /// Arguments that start with the shortname of a registered option are never considered a number.
+    /// 
+    /// **Arguments**
+    ///  * `arg`: The argument to check.
+    /// 
+    /// **Returns**  
+    /// Whether or not the argument should be treated as a negative number.
+    fn is_negative_number(&self, arg: &str) -> bool {
+        // Check if it's a dash followed by only digits and decimal points
+        let number = match arg.strip_prefix('-') { Some(number) => number, None => { return false; } };
+        if !number.chars().any(|c| c.is_ascii_digit()) || !number.chars().all(|c| c.is_ascii_digit() || c == '.') { return false; }
+
+        // Make sure it doesn't clash with an option
+        let first = &number[..1];
+        return !self.options.iter().any(|o| self.names_eq(&o.shortname, first) || o.short_aliases.iter().any(|a| self.names_eq(a, first)));
+    }
+
+    /// Computes the edit (Levenshtein) distance between two strings, i.e., the number of character insertions, deletions or substitutions needed to go from one to the other.
+    /// 
+    /// **Arguments**
+    ///  * `a`: The first string.
+    ///  * `b`: The second string.
+    /// 
+    /// **Returns**  
+    /// The edit distance between the two strings.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+
+        // Keep track of only the previous row of the distance matrix
+        let mut prev: Vec<usize> = (0..b.len() + 1).collect();
+        let mut curr: Vec<usize> = vec!(0; b.len() + 1);
+        for i in 1..a.len() + 1 {
+            curr[0] = i;
+            for j in 1..b.len() + 1 {
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+                curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        // Done
+        return prev[b.len()];
+    }
+
+    /// Helper function that finds the longname of the option closest to the given unknown option, if any is close enough.
+    /// 
+    /// **Arguments**
+    ///  * `arg`: The unknown option as given by the user (including dashes and any value after an '=').
+    /// 
+    /// **Returns**  
+    /// The longname (including dashes) of the closest option if its edit distance is less than 3, or None otherwise.
+    fn suggest_option(&self, arg: &str) -> std::option::Option<String> {
+        // Strip the dashes (or other prefixes) and any value
+        let name = arg.trim_start_matches(|c| self.opt_prefixes.contains(&c));
+        let name = match name.find('=') { Some(pos) => &name[..pos], None => name };
+        // Single characters are too short to say anything sensible about
+        if name.chars().count() <= 1 { return None; }
+
+        // Find the closest longname
+        let mut best: std::option::Option<(usize, &str)> = None;
+        for o in self.options.iter().filter(|o| !o.hidden) {
+            let distance = if self.case_insensitive { ArgParser::edit_distance(&name.to_lowercase(), &o.longname.to_lowercase()) } else { ArgParser::edit_distance(name, &o.longname) };
+            if distance < 3 && (best.is_none() || distance < best.unwrap().0) {
+                best = Some((distance, &o.longname));
+            }
+        }
+
+        // Return it as a suggestion
+        return best.map(|(_, longname)| format!("--{}", longname));
+    }
+
+    /// Generates a string of n spaces.
+    /// 
+    /// **Arguments**
+    ///  * `N`: The number of spaces to generate.
+    /// 
+    /// **Returns**  
+    /// A string with N spaces.
+    fn generate_spaces(n: usize) -> String {
+        // Create a large enough string
+        let mut result: String = String::new();
+        result.reserve(n);
+
+        // Write the spaces
+        for _ in 0..n {
+            result.push(' ');
+        }
+
+        // Done
+        return result;
+    }
+
+    /// Helper function that adds the given description linewrapped to the given string.
+    /// 
+    /// **Arguments**
+    ///  * `result`: The string to append the result to.
+    ///  * `x`: The current column position on the line. Will be updated as we write.
+    ///  * `description`: The description to write.
+    ///  * `indent_width`: The width before each new line.
+    ///  * `line_width`: The line width to break on.
+    ///  * `tab_width`: The distance between tab stops. Must be larger than 0.
+    ///  * `separators`: The graphemes that separate words. Any other than whitespace are written as-is, but lines may be broken after them.
+    fn print_description(result: &mut String, x: &mut usize, description: &str, indent_width: usize, line_width: usize, tab_width: usize, separators: &[&str]) {
+        // Make sure indent_width and line_width aren't conflicting
+        if indent_width >= line_width {
+            panic!("Cannot have an indent width larger than or equal to a line width: {} >= {}", indent_width, line_width);
+        }
+
+        // Generate the indent spaces
+        let indent = ArgParser::generate_spaces(indent_width);
+
+        // Go through the description word-by-word
+        for (word, separator) in WordIterator::with_separators(description, separators) {
+            // Wrap the word in an OpString
+            let oword = OpString::new(word);
+
+            // Only do stuff if the parsed word has at least one char
+            if word.len() > 0 {
+                // See if we need to go to the next line
+                if *x != indent_width && *x + word.len() + 1 >= line_width {
+                    // Add a new line plus the indent
+                    result.reserve(1 + indent_width);
+                    result.push('\n');
+                    result.push_str(indent.as_str());
+
+                    // Reset the x
+                    *x = indent_width;
+                }
+
+                // Now loop through the word to write it, possibly linewrapped
+                result.reserve(word.len() + word.len() / (line_width - indent_width));
+                for c in oword.chars() {
+                    // Split if needed
+                    if *x >= line_width {
+                        // Add a new line plus the indent
+                        result.reserve(1 + indent_width);
+                        result.push('\n');
+                        result.push_str(indent.as_str());
+
+                        // Reset the x
+                        *x = indent_width;
+                    }
+
+                    // Write the letter
+                    result.push_str(c);
+                    *x += 1;
+                }
+            }
+
+            // After this word, print the needed stuff
+            if separator.eq(" ") && *x + 1 + 1 < line_width {
+                // Simply print the space
+                result.push(' ');
+                *x += 1;
+            } else if separator.eq("\n") {
+                // Print a newline
+                result.reserve(1 + indent_width);
+                result.push('\n');
+                result.push_str(indent.as_str());
+
+                // Reset the x
+                *x = indent_width;
+            } else if separator.eq("\r") {
+                // Ignore
+                continue;
+            } else if separator.eq("\t") {
+                // Print enough spaces to get to the next tab stop
+                let target_x = *x - (*x % tab_width) + tab_width;
+                // Don't do it if we go too large
+                if target_x + 1 >= line_width { continue; }
+                // Print the spaces
+                result.push_str(ArgParser::generate_spaces(target_x - *x).as_str());
+                *x = target_x;
+            } else if separator.eq("\0") {
+                // Stop
+                break;
+            } else if separator.ne(" ") {
+                // Any other separator is written like a single-character word
+                if *x >= line_width {
+                    result.reserve(1 + indent_width);
+                    result.push('\n');
+                    result.push_str(indent.as_str());
+                    *x = indent_width;
+                }
+                result.push_str(separator);
+                *x += 1;
+            }
+        }
+    }
+
+    /// Helper function that prints the given positional to the given string, neatly formatted and line-wrapped.  
+    /// Note that the string will be assuming it is written after a newline, and will terminate itself with newlines too.
+    /// 
+    /// Note that this function will panic! is the given uid doesn't exists.
+    ///
+    /// **Arguments**
+    ///  * `result`: The resulting string to write to.
+    ///  * `uid': The uid of the positional to write its help string for.
+    ///  * `indent_width`: The prefix width of each new line. Also the space positionals have before they interrupt the description column.
+    ///  * `line_width`: The total line width of each line.
+    fn print_pos_help(&self, result: &mut String, uid: &str, indent_width: usize, line_width: usize) {
+        // Try to find the positional
+        let mut opt_pos: std::option::Option<&Positional> = None;
+        for p in self.positionals.iter() {
+            if p.uid.eq(uid) {
+                opt_pos = Some(p);
+                break;
+            }
+        }
+        if let None = opt_pos { panic!("Unknown positional '{}'.", uid); }
+        let pos = opt_pos.unwrap();
+
+        // Prepare the argument string and write it
+        let pos_name = self.pos_signature(pos);
+        result.push_str(pos_name.as_str());
+
+        // Either pad the string until the description column, or add a newline
+        if 2 + pos_name.len() >= indent_width {
+            // Add a new line plus the indent
+            result.reserve(1 + indent_width);
+            result.push('\n');
+            result.push_str(ArgParser::generate_spaces(indent_width).as_str());
+        } else {
+            result.push_str(ArgParser::generate_spaces(indent_width - pos_name.len()).as_str());
+        }
+
+        // Add the choices to the description, if any
+        let mut description = pos.description.clone();
+        if pos.choices.len() > 0 {
+            if self.annotate_help { description.push_str(format!(" (choices: {})", pos.choices.join(", ")).as_str()); }
+            else { description.push_str(format!("\nChoices: {}", pos.choices.join(", ")).as_str()); }
+        }
+
+        // Start writing the lines, linewrapped
+        let mut x: usize = indent_width;
+        let separators: Vec<&str> = self.word_separators.iter().map(|sep| sep.as_str()).collect();
+        ArgParser::print_description(result, &mut x, description.as_str(), indent_width, line_width, self.tab_width, &separators);
+
+        // Write a final newline character and we're done
+        result.push('\n');
+    }
+
+    /// Helper function that generates the name of the given positional as shown in the help string, including the indent before it.
+    /// 
+    /// **Arguments**
+    ///  * `pos`: The positional to generate the name of.
+    /// 
+    /// **Returns**  
+    /// The name of the positional (e.g., '  <pos1>').
+    fn pos_signature(&self, pos: &Positional) -> String {
+        format!("  {}", self.pos_name(pos))
+    }
+
+    /// Helper function that generates the name of the given positional as shown in the usage and help strings, i.e., its metavar or name in the configured brackets.
+    /// 
+    /// **Arguments**
+    ///  * `pos`: The positional to generate the name of.
+    /// 
+    /// **Returns**  
+    /// The name of the positional (e.g., '<pos1>', or '<pos1...>' if it is variadic).
+    fn pos_name(&self, pos: &Positional) -> String {
+        let name = format!("{}{}", pos.metavar.as_ref().unwrap_or(&pos.name), if pos.multi { "..." } else { "" });
+        match self.pos_brackets {
+            BracketStyle::Angle  => format!("<{}>", name),
+            BracketStyle::Square => format!("[{}]", name),
+            BracketStyle::Plain  => name,
+        }
+    }
+
+    /// Helper function that generates the names and parameters of the given option as shown in the help string, including the indent before it.
+    /// 
+    /// **Arguments**
+    ///  * `opt`: The option to generate the signature of.
+    /// 
+    /// **Returns**  
+    /// The signature of the option (e.g., '  -o,--opt1 <value>').
+    fn opt_signature(&self, opt: &Option) -> String {
+        let aliases: String = if self.show_aliases { opt.aliases.iter().map(|a| format!(",--{}", a)).collect() } else { String::new() };
+        let short_aliases: String = if self.show_aliases { opt.short_aliases.iter().map(|a| format!("-{},", a)).collect() } else { String::new() };
+        format!("  {}{}--{}{}{}{}", if opt.shortname.len() > 0 { format!("-{},", opt.shortname) } else { String::new() }, short_aliases, if opt.negatable { "[no-]" } else { "" }, opt.longname, aliases, if opt.param_description.len() > 0 { format!(" {}", opt.param_description) } else { String::new() })
+    }
+
+    /// Helper function that computes the narrowest indent width for which all positionals and options fit before the description column, up to `MAX_AUTO_INDENT_WIDTH`.
+    /// 
+    /// **Returns**  
+    /// The computed indent width, or the default indent width if there are no positionals and options.
+    fn auto_indent_width(&self) -> usize {
+        // Find the longest name, taking into account that the print functions require some space before the description
+        let longest: std::option::Option<usize> = self.positionals.iter().filter(|p| !p.hidden).map(|p| self.pos_signature(p).len())
+            .chain(self.options.iter().filter(|o| !o.hidden).map(|o| self.opt_signature(o).len()))
+            .max();
+        match longest {
+            Some(longest) => (longest + 3).min(MAX_AUTO_INDENT_WIDTH),
+            None          => DEFAULT_INDENT_WIDTH,
+        }
+    }
+
+    /// Helper function that prints the given option to the given string, neatly formatted and line-wrapped.  
+    /// Note that the string will be assuming it is written after a newline, and will terminate itself with newlines too.
+    /// 
+    /// Note that this function will panic! is the given uid doesn't exists.
+    ///
+    /// **Arguments**
+    ///  * `result`: The resulting string to write to.
+    ///  * `uid': The uid of the option to write its help string for.
+    ///  * `indent_width`: The prefix width of each new line. Also the space options have before they interrupt the description column.
+    ///  * `line_width`: The total line width of each line.
+    fn print_opt_help(&self, result: &mut String, uid: &str, indent_width: usize, line_width: usize) {
+        // Try to find the positional
+        let mut opt_opt: std::option::Option<&Option> = None;
+        for o in self.options.iter() {
+            if o.uid.eq(uid) {
+                opt_opt = Some(o);
+                break;
+            }
+        }
+        if let None = opt_opt { panic!("Unknown option '{}'.", uid); }
+        let opt = opt_opt.unwrap();
+
+        // Prepare the argument string and write it
+        let opt_name = self.opt_signature(opt);
+        result.push_str(opt_name.as_str());
+
+        // Either pad the string until the description column, or add a newline
+        if 2 + opt_name.len() >= indent_width {
+            // Add a new line plus the indent
+            result.reserve(1 + indent_width);
+            result.push('\n');
+            result.push_str(ArgParser::generate_spaces(indent_width).as_str());
+        } else {
+            result.push_str(ArgParser::generate_spaces(indent_width - opt_name.len()).as_str());
+        }
+
+        // Add the default, the choices and whether it's repeatable to the description, if any
+        let mut description = opt.description.clone();
+        if self.annotate_help {
+            if let Some(default) = &opt.default_value {
+                if opt.max_n_values > 0 { description.push_str(format!(" (default: {})", default).as_str()); }
+            }
+            if opt.choices.len() > 0 { description.push_str(format!(" (choices: {})", opt.choices.join(", ")).as_str()); }
+        } else if opt.choices.len() > 0 {
+            description.push_str(format!("\nChoices: {}", opt.choices.join(", ")).as_str());
+        }
+        if opt.counted || opt.repeatable { description.push_str("\nMay be repeated."); }
+
+        // Start writing the lines, linewrapped
+        let mut x: usize = indent_width;
+        let separators: Vec<&str> = self.word_separators.iter().map(|sep| sep.as_str()).collect();
+        ArgParser::print_description(result, &mut x, description.as_str(), indent_width, line_width, self.tab_width, &separators);
+
+        // Write a final newline character and we're done
+        result.push('\n');
+    }
+
+
+
+    /// Registers a new positional argument, returning an error instead of panicking if that fails.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    /// 
+    /// **Returns**  
+    /// Nothing if the registration succeeded, or a description of why it failed otherwise.
+    pub fn try_add_pos(&mut self, uid: &str, name: &str, description: &str) -> Result<(), String> {
+        // Check if the uid conflicts
+        for pos in self.positionals.iter() {
+            if pos.uid == uid {
+                return Err(format!("A positional with uid '{}' already exists in this ArgParser instance.", uid));
+            }
+        }
+
+        // Create a new Positional argument
+        let result = Positional {
+            uid: String::from(uid),
+            index: self.positionals.len(),
+            name: String::from(name),
+            description: String::from(description),
+            multi: false,
+            optional: false,
+            choices: Vec::new(),
+            validator: None,
+            metavar: None,
+            hidden: false,
+        };
+
+        // Store the positional internally
+        self.positionals.push(result);
+
+        // Done
+        return Ok(());
+    }
+
+    /// Registers a new positional argument.
+    /// 
+    /// Panics if the registration fails; use `try_add_pos()` to handle that gracefully instead.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_pos(&mut self, uid: &str, name: &str, description: &str) -> &mut ArgParser {
+        // Register it, panicking if that fails
+        if let Err(err) = self.try_add_pos(uid, name, description) {
+            panic!("{}", err);
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new positional argument whose value is restricted to the given choices.
+    /// 
+    /// Any other value results in an error. The choices are listed in the help string.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    ///  * `choices`: The values the positional may have.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_pos_choices(&mut self, uid: &str, name: &str, description: &str, choices: Vec<String>) -> &mut ArgParser {
+        // Register the positional as usual
+        self.add_pos(uid, name, description);
+
+        // Set the choices
+        self.positionals.last_mut().unwrap().choices = choices;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new positional argument whose value is checked by the given function.
+    /// 
+    /// If the function returns an error for the value, parsing fails with an error that includes the returned description.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    ///  * `validator`: The function that checks the value. Should return a description of what's wrong if the value isn't valid.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_pos_validated(&mut self, uid: &str, name: &str, description: &str, validator: fn(&str) -> Result<(), String>) -> &mut ArgParser {
+        // Register the positional as usual
+        self.add_pos(uid, name, description);
+
+        // Set the validator
+        self.positionals.last_mut().unwrap().validator = Some(validator);
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new optional positional argument.
+    /// 
+    /// Optional positionals are only given a value if there are enough values to fill all required positionals too. For example, with the positionals `<a> [<b>] <c>`, two values are given to 'a' and 'c', while three values are given to 'a', 'b' and 'c'. Leaving it out does not result in a warning.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_pos_optional(&mut self, uid: &str, name: &str, description: &str) -> &mut ArgParser {
+        // Make sure it doesn't come after a variadic positional, since then it's ambiguous which of the two gets the values
+        if let Some(pos) = self.positionals.iter().find(|p| p.multi) {
+            panic!("Cannot add optional positional '{}' after variadic positional '{}'.", uid, pos.uid);
+        }
+
+        // Register the positional as usual
+        self.add_pos(uid, name, description);
+
+        // Mark it as optional
+        self.positionals.last_mut().unwrap().optional = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new variadic positional argument, which collects all remaining positional values.
+    /// 
+    /// There can be only one such positional. Normal positionals may be registered after it (but optional ones may not), in which case the last values are given to those instead (e.g., `<files...> <out>`). Options in between its values are still parsed as options (unless they appear after the double dash). Use `dict.get_pos_multi()` to get all of its values.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
+    ///  * `name`: Readable name for use in the usage/help string.
+    ///  * `description`: A string description of the positional.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_pos_multi(&mut self, uid: &str, name: &str, description: &str) -> &mut ArgParser {
+        // Make sure there is no other variadic one
+        if let Some(pos) = self.positionals.iter().find(|p| p.multi) {
+            panic!("Cannot add variadic positional '{}'; there already is variadic positional '{}'.", uid, pos.uid);
+        }
+
+        // Register the positional as usual
+        self.add_pos(uid, name, description);
+
+        // Mark it as variadic
+        self.positionals.last_mut().unwrap().multi = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets the name shown for the given positional in the usage and help strings, e.g., 'FILE' instead of its name.
+    /// 
+    /// The metavar is still enclosed in brackets as set with `set_pos_bracket_style()`.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the positional to change. Must refer to an already registered positional.
+    ///  * `metavar`: The name to show instead.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_metavar(&mut self, uid: &str, metavar: String) -> &mut ArgParser {
+        // Try to find the positional
+        match self.positionals.iter_mut().find(|p| p.uid.eq(uid)) {
+            Some(pos) => { pos.metavar = Some(metavar); },
+            None      => { panic!("Cannot set metavar for unknown positional '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Checks whether an option with the given uid, shortname and longname would conflict with any of the already registered options.
+    /// 
+    /// This is used by all option registrations (including built-in ones like help, negatable flags and aliases), so conflicts are found regardless of the order in which options are registered.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the new option, or None if only names are registered (e.g., for an alias).
+    ///  * `shortname`: The shortname of the new option, or an empty string if it has none.
+    ///  * `longname`: The longname of the new option, or an empty string if it has none (e.g., for a short alias).
+    ///  * `negatable`: Whether the new option is a negatable flag, in which case its negated longname ('no-' followed by the longname) may not conflict either.
+    /// 
+    /// **Returns**  
+    /// Nothing if there are no conflicts, or a description of the first conflict found otherwise.
+    fn check_conflicts(&self, uid: std::option::Option<&str>, shortname: &str, longname: &str, negatable: bool) -> Result<(), String> {
+        let neg_longname = format!("no-{}", longname);
+        let longnames: Vec<&str> = if negatable { vec!(longname, &neg_longname) } else { vec!(longname) };
+        for opt in self.options.iter() {
+            if let Some(uid) = uid {
+                if opt.uid.eq(uid) {
+                    return Err(format!("An option with uid '{}' already exists in this ArgParser instance.", uid));
+                }
+            }
+            if shortname.len() > 0 && (opt.shortname.eq(shortname) || opt.short_aliases.iter().any(|a| a.eq(shortname))) {
+                return Err(format!("An option with shortlabel '{}' already exists in this ArgParser instance.", shortname));
+            }
+            for name in longnames.iter() {
+                if name.len() > 0 && (opt.longname.eq(name) || opt.aliases.iter().any(|a| a.eq(name)) || (opt.negatable && format!("no-{}", opt.longname).eq(name))) {
+                    return Err(format!("An option with longname '{}' already exists in this ArgParser instance.", name));
                 }
             }
+        }
+        return Ok(());
+    }
+
+    /// Checks whether the given longname (or alias) is valid, i.e., it is not empty and does not contain an equals sign.
+    /// 
+    /// **Arguments**
+    ///  * `longname`: The longname to check.
+    /// 
+    /// **Returns**  
+    /// Nothing if the longname is valid, or a description of why it isn't otherwise.
+    fn check_longname(longname: &str) -> Result<(), String> {
+        if longname.len() == 0 {
+            return Err(String::from("A longname cannot be empty."));
+        }
+        if longname.contains('=') {
+            return Err(format!("A longname cannot contain an equals sign: '{}'.", longname));
+        }
+        return Ok(());
+    }
+
+    /// Registers a new option, returning an error instead of panicking if that fails.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options, and cannot be empty or contain an equals sign.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// Nothing if the registration succeeded, or a description of why it failed otherwise.
+    pub fn try_add_opt(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str) -> Result<(), String> {
+        // Check if the shortname is valid
+        let oshortname = OpString::new(shortname);
+        if oshortname.len() > 1 {
+            return Err(format!("A shortlabel cannot have more than one character: {} > 1.", shortname.len()));
+        }
+
+        // Check if the longname is valid
+        ArgParser::check_longname(longname)?;
+
+        // Check if the uid, shortname or longnames are in conflict
+        self.check_conflicts(Some(uid), shortname, longname, false)?;
+
+        // Make sure the max_n_values isn't smaller
+        if max_n_values < min_n_values {
+            return Err(format!("max_n_values has to be equal to or larger than min_n_values; {} > {}", max_n_values, min_n_values));
+        }
+
+        // Create a new Option
+        let result = Option {
+            uid               : String::from(uid),
+            shortname         : String::from(shortname),
+            longname          : String::from(longname),
+            min_n_values,
+            max_n_values,
+            param_description : String::from(param_description),
+            description       : String::from(description),
+            env_var           : String::new(),
+            negatable         : false,
+            required          : false,
+            choices           : Vec::new(),
+            counted           : false,
+            map               : false,
+            repeatable        : false,
+            group             : String::new(),
+            aliases           : Vec::new(),
+            short_aliases     : Vec::new(),
+            value_delimiter   : None,
+            keep_empty_values : false,
+            value_terminator  : None,
+            allow_dash_values : false,
+            int_range         : None,
+            require_if_min    : false,
+            from_file         : None,
+            hidden            : false,
+            rest              : false,
+            default_value     : None,
+        };
+
+        // Store the option intenally
+        self.options.push(result);
+
+        // Done
+        return Ok(());
+    }
+
+    /// Registers a new option.
+    /// 
+    /// Panics if the registration fails; use `try_add_opt()` to handle that gracefully instead.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str) -> &mut ArgParser {
+        // Register it, panicking if that fails
+        if let Err(err) = self.try_add_opt(uid, shortname, longname, min_n_values, max_n_values, param_description, description) {
+            panic!("{}", err);
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that is parsed as usual, but left out of the usage and help strings and the completions, e.g., for deprecated or experimental options.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_hidden(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str) -> &mut ArgParser {
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, min_n_values, max_n_values, param_description, description);
+
+        // Hide it
+        self.options.last_mut().unwrap().hidden = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that falls back to the value of an environment variable if it isn't given on the command line.
+    /// 
+    /// If the option is given on the command line, the environment variable is ignored. Otherwise, if the variable is set, its value is split on whitespace to form the option's values (or stored as a whole if the option accepts at most one value). If the option accepts no values, it is given unless the variable is empty or set to '0', 'false' or 'no' (case-insensitively). Note that the values read from the environment are validated against `min_n_values` and `max_n_values` like any other.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    ///  * `env_var`: The name of the environment variable to fall back to.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_env(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str, env_var: &str) -> &mut ArgParser {
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, min_n_values, max_n_values, param_description, description);
+
+        // Set the environment variable
+        self.options.last_mut().unwrap().env_var = String::from(env_var);
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets a default for the given option, which is used if the option is given neither on the command line nor in its environment variable (if any).
+    /// 
+    /// The default is split into values like the value of an environment variable is (see `add_opt_env()`), and validated like any other values.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to set the default of. Must refer to an already registered option.
+    ///  * `value`: The default value.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_default(&mut self, uid: &str, value: &str) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.default_value = Some(String::from(value)); },
+            None      => { panic!("Cannot set default of unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Reads the defaults for options from the given file (see `set_default()`).
+    /// 
+    /// The file contains a 'key = value' pair on each line, where the key is the uid of an option. Empty lines and lines starting with '#' are ignored. For options that accept no values, the value must be either 'true' (to give it by default) or 'false' (to not give it).
+    /// 
+    /// **Arguments**
+    ///  * `path`: The path of the file to read.
+    /// 
+    /// **Returns**  
+    /// Nothing if the defaults were read successfully, or a description of what went wrong otherwise. In the latter case, none of the defaults are set.
+    pub fn load_defaults(&mut self, path: &str) -> Result<(), String> {
+        // Read the file
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err)     => { return Err(format!("Could not read defaults file '{}': {}", path, err)); }
+        };
+
+        // Go through it line-by-line
+        let mut defaults: Vec<(usize, std::option::Option<String>)> = Vec::new();
+        let mut errors: Vec<String> = Vec::new();
+        let mut unknown: Vec<String> = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            // Skip empty lines and comments
+            let line = line.trim();
+            if line.len() == 0 || line.starts_with('#') { continue; }
+
+            // Split it into a key and a value
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None               => { errors.push(format!("line {} is not a 'key = value' pair", i + 1)); continue; }
+            };
+
+            // Find the option
+            let opt_i = match self.options.iter().position(|o| o.uid.eq(key)) {
+                Some(opt_i) => opt_i,
+                None        => { unknown.push(String::from(key)); continue; }
+            };
+
+            // Remember the default (taking flags into account)
+            if self.options[opt_i].max_n_values > 0 {
+                defaults.push((opt_i, Some(String::from(value))));
+            } else if value.eq("true") {
+                defaults.push((opt_i, Some(String::new())));
+            } else if value.eq("false") {
+                defaults.push((opt_i, None));
+            } else {
+                errors.push(format!("line {} gives '{}' to flag '{}', expected 'true' or 'false'", i + 1, value, key));
+            }
+        }
+
+        // Report any problems
+        if unknown.len() > 0 { errors.push(format!("unknown options {}", unknown.iter().map(|u| format!("'{}'", u)).collect::<Vec<String>>().join(", "))); }
+        if errors.len() > 0 {
+            return Err(format!("Invalid defaults file '{}': {}.", path, errors.join("; ")));
+        }
+
+        // Only set the defaults once we know the whole file is valid
+        for (opt_i, value) in defaults {
+            self.options[opt_i].default_value = value;
+        }
+        return Ok(());
+    }
+
+    /// Registers a new option that must always be given by the user.
+    /// 
+    /// If the option is missing after parsing, an error is generated (unless help is given).
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    ///  * `required`: Whether or not the option is required.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_required(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str, required: bool) -> &mut ArgParser {
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, min_n_values, max_n_values, param_description, description);
+
+        // Set whether it's required
+        self.options.last_mut().unwrap().required = required;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option whose values are restricted to a fixed set of choices.
+    /// 
+    /// Any value given that isn't exactly (i.e., case-sensitively) one of the choices results in an error. The choices are also listed in the help string.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
+    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
+    ///  * `description`: A string description of the option.
+    ///  * `choices`: The list of values that are allowed for this option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_choices(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str, choices: Vec<String>) -> &mut ArgParser {
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, min_n_values, max_n_values, param_description, description);
+
+        // Set the choices
+        self.options.last_mut().unwrap().choices = choices;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that takes a single integer within the given range.
+    /// 
+    /// Any value that is not an integer, or that is outside of the range, results in an error. Use `dict.get_opt_as::<i64>()` to get the value.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `param_description`: A string description of the parameter of this option.
+    ///  * `description`: A string description of the option.
+    ///  * `min`: The smallest value allowed (inclusive).
+    ///  * `max`: The largest value allowed (inclusive). Cannot be smaller than `min`.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_int_range(&mut self, uid: &str, shortname: &str, longname: &str, param_description: &str, description: &str, min: i64, max: i64) -> &mut ArgParser {
+        // Make sure the range makes sense
+        if max < min {
+            panic!("max has to be equal to or larger than min; {} > {}", min, max);
+        }
+
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, 1, 1, param_description, description);
+
+        // Set the range
+        self.options.last_mut().unwrap().int_range = Some((min, max));
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that takes a single path, of which the contents are used as its value instead, e.g., to give a password with '--password-file secret.txt'.
+    /// 
+    /// The file is read after parsing, so this also works for its default and environment variable. If it can't be read, an error is given. Use `dict.get_opt_one()` to get the contents.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `param_description`: A string description of the parameter of this option.
+    ///  * `description`: A string description of the option.
+    ///  * `trim`: Whether or not to remove any leading and trailing whitespace (such as a final newline) from the contents.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_file(&mut self, uid: &str, shortname: &str, longname: &str, param_description: &str, description: &str, trim: bool) -> &mut ArgParser {
+        // Register the option as usual
+        self.add_opt(uid, shortname, longname, 1, 1, param_description, description);
+
+        // Mark it as reading from a file
+        self.options.last_mut().unwrap().from_file = Some(trim);
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that takes a single boolean value, such as '--enabled true' or '--enabled no'.
+    /// 
+    /// Unlike a flag, the value is given explicitly. Use `dict.get_bool()` to get it.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    #[inline]
+    pub fn add_opt_bool(&mut self, uid: &str, shortname: &str, longname: &str, description: &str) -> &mut ArgParser {
+        self.add_opt(uid, shortname, longname, 1, 1, "<true|false>", description)
+    }
+
+    /// Registers a new option that collects 'key=value' pairs into a map, such as a define (e.g., '-D a=1 -D b=2').
+    /// 
+    /// The option may be repeated, and each occurrence takes exactly one 'key=value' value (which may also be given immediately, e.g., '-Da=1'). Values without an equals sign result in an error. Use `dict.get_opt_map()` to get the resulting map.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `param_description`: A string description of the parameters of this option. Will most likely be something like `<key>=<value>`.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_map(&mut self, uid: &str, shortname: &str, longname: &str, param_description: &str, description: &str) -> &mut ArgParser {
+        // Register the option as usual, accepting any number of values
+        self.add_opt(uid, shortname, longname, 1, usize::MAX, param_description, description);
+
+        // Mark it as a map
+        self.options.last_mut().unwrap().map = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new option that takes all arguments after it as its values, verbatim, such as a command to run (e.g., '--exec ls -la').
+    /// 
+    /// Once the option is given, nothing after it is parsed anymore, even if it looks like an option. Its values can be retrieved with `dict.get_opt()` as usual.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
+    ///  * `description`: A string description of the option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_rest(&mut self, uid: &str, shortname: &str, longname: &str, description: &str) -> &mut ArgParser {
+        // Register the option as usual, accepting any number of values
+        self.add_opt(uid, shortname, longname, 0, usize::MAX, "<args...>", description);
+
+        // Mark it as taking the rest
+        self.options.last_mut().unwrap().rest = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new flag that counts how often it is given, such as a verbosity flag.
+    /// 
+    /// Every occurrence counts, including repeats of the shortname in a single argument (e.g., '-vvv' counts as three). Use `dict.get_count()` to query how often it was given.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the flag. Must be unique across all options. If you don't want to use it, pass a new/empty string.
+    ///  * `longname`: A multi-character identifier for the flag. Must be unique across all options.
+    ///  * `description`: A string description of the flag.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_count(&mut self, uid: &str, shortname: &str, longname: &str, description: &str) -> &mut ArgParser {
+        // Register the flag as usual
+        self.add_opt(uid, shortname, longname, 0, 0, "", description);
+
+        // Mark it as counted
+        self.options.last_mut().unwrap().counted = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new flag that can be negated.
+    /// 
+    /// Next to `--<longname>`, the flag may also be given as `--no-<longname>` to explicitly set it to false. If both are given, the last one wins. Use `dict.get_flag()` to query its state.
+    /// 
+    /// ** Arguments **
+    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
+    ///  * `shortname`: A single character, optional identifier for the flag. Must be unique across all options. If you don't want to use it, pass a new/empty string. Always sets the flag to true.
+    ///  * `longname`: A multi-character identifier for the flag. Must be unique across all options, and so must its negated form.
+    ///  * `description`: A string description of the flag.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_flag(&mut self, uid: &str, shortname: &str, longname: &str, description: &str) -> &mut ArgParser {
+        // Check if the flag (including its negated longname) is in conflict
+        if let Err(err) = self.check_conflicts(Some(uid), shortname, longname, true) {
+            panic!("{}", err);
+        }
+
+        // Register the flag as usual
+        self.add_opt(uid, shortname, longname, 0, 0, "", description);
+
+        // Mark it as negatable
+        self.options.last_mut().unwrap().negatable = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers an alternative longname for the given option.
+    /// 
+    /// Aliases are matched like the option's own longname, but errors always use the original one. By default, aliases are not shown in the help string; use `set_show_aliases()` to change that.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to add an alias to. Must refer to an already registered option.
+    ///  * `alias_longname`: The alternative longname. Must be unique across all options (including their aliases), and cannot be empty or contain an equals sign.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_alias(&mut self, uid: &str, alias_longname: &str) -> &mut ArgParser {
+        // Check if the alias is valid and not in conflict
+        if let Err(err) = ArgParser::check_longname(alias_longname).and_then(|_| self.check_conflicts(None, "", alias_longname, false)) {
+            panic!("{}", err);
+        }
+
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.aliases.push(String::from(alias_longname)); },
+            None      => { panic!("Cannot add alias to unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers an alternative shortname for the given option (e.g., '-?' next to '-h').
+    /// 
+    /// Like aliases, short aliases are matched like the option's own shortname, but errors always use the original one. They are shown in the help string only if `set_show_aliases()` is used.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to add a short alias to. Must refer to an already registered option.
+    ///  * `alias_shortname`: The alternative shortname. Must be a single character, and unique across all options (including their short aliases).
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_short_alias(&mut self, uid: &str, alias_shortname: &str) -> &mut ArgParser {
+        // Check if the alias is valid
+        if OpString::new(alias_shortname).len() != 1 {
+            panic!("A short alias must be exactly one character, not '{}'.", alias_shortname);
+        }
+
+        // Check if the alias is in conflict
+        if let Err(err) = self.check_conflicts(None, alias_shortname, "", false) {
+            panic!("{}", err);
+        }
+
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.short_aliases.push(String::from(alias_shortname)); },
+            None      => { panic!("Cannot add short alias to unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the aliases of options are listed in the help string.
+    /// 
+    /// **Arguments**
+    ///  * `show_aliases`: Whether or not to list the aliases.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_show_aliases(&mut self, show_aliases: bool) -> &mut ArgParser {
+        // Simply store it
+        self.show_aliases = show_aliases;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets a delimiter for the values of the given option, splitting each value given into multiple values (e.g., '--tags a,b,c' results in three values).
+    /// 
+    /// Splitting happens before the number of values is checked, so `min_n_values` and `max_n_values` apply to the values after splitting.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to split the values of. Must refer to an already registered option.
+    ///  * `delimiter`: The character to split on.
+    ///  * `keep_empty`: Whether or not to keep empty values that result from splitting (e.g., '--tags a,,b' results in three values if true, or two if false).
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_value_delimiter(&mut self, uid: &str, delimiter: char, keep_empty: bool) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => {
+                opt.value_delimiter   = Some(delimiter);
+                opt.keep_empty_values = keep_empty;
+            },
+            None => { panic!("Cannot set value delimiter of unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets a terminator for the values of the given option, which ends its values early when given (e.g., '--files a b ; c' with ';' as terminator gives the option two values and leaves 'c' as a positional).
+    /// 
+    /// The terminator itself is consumed, but not stored as a value. It is also consumed if it directly follows the last value when the option already has `max_n_values` values.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to set the terminator of. Must refer to an already registered option.
+    ///  * `token`: The argument that ends the values.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_value_terminator(&mut self, uid: &str, token: String) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.value_terminator = Some(token); },
+            None      => { panic!("Cannot set value terminator of unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the given option must be given if it needs at least one value.
+    /// 
+    /// By default, an option with a positive `min_n_values` only needs that many values if it's given at all. If enabled, leaving it out results in an error too, as if it was registered with `add_opt_required()`. For options with a `min_n_values` of 0, this has no effect.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to change. Must refer to an already registered option.
+    ///  * `require_if_min`: Whether or not the option must be given if it needs values.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_require_if_min(&mut self, uid: &str, require_if_min: bool) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.require_if_min = require_if_min; },
+            None      => { panic!("Cannot set require-if-min of unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the given option and/or positional is left out of the usage and help strings and the completions. It is still parsed as usual.
+    /// 
+    /// Because options and positionals don't share uids, this changes both the option and the positional with the given uid, if they exist.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option or positional to change. Must refer to an already registered option or positional.
+    ///  * `hidden`: Whether or not to hide it.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_hidden(&mut self, uid: &str, hidden: bool) -> &mut ArgParser {
+        // Try to find the option and the positional
+        let mut found = false;
+        if let Some(opt) = self.options.iter_mut().find(|o| o.uid.eq(uid)) { opt.hidden = hidden; found = true; }
+        if let Some(pos) = self.positionals.iter_mut().find(|p| p.uid.eq(uid)) { pos.hidden = hidden; found = true; }
+        if !found { panic!("Cannot hide unknown option or positional '{}'.", uid); }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the given option may be given multiple times, each occurrence taking between `min_n_values` and `max_n_values` values, e.g., so '-I/usr/include -I /opt/include' gives both paths.
+    /// 
+    /// The values of all occurrences are collected in order, regardless of whether they are given immediately (e.g., '-Ifoo' or '--include=foo') or as separate arguments. Without this, an option that accepts at most one value may only be given once.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to change. Must refer to an already registered option.
+    ///  * `repeatable`: Whether or not the option may be repeated.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_repeatable(&mut self, uid: &str, repeatable: bool) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.repeatable = repeatable; },
+            None      => { panic!("Cannot make unknown option '{}' repeatable.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the given option takes values that look like options, e.g., so '--message --verbose' gives '--verbose' as the message.
+    /// 
+    /// Such arguments are only taken while the option accepts more values. If the double dash is used, it still separates the options from the positionals instead of being taken as a value.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to change. Must refer to an already registered option.
+    ///  * `allow_dash_values`: Whether or not to take values that look like options.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_allow_dash_values(&mut self, uid: &str, allow_dash_values: bool) -> &mut ArgParser {
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.allow_dash_values = allow_dash_values; },
+            None      => { panic!("Cannot allow dash values for unknown option '{}'.", uid); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new group of options, which is listed under its own heading in the help string.
+    /// 
+    /// Groups are listed in the order they are registered, after the options that are not in any group. Use `set_opt_group()` to add options to it.
+    /// 
+    /// **Arguments**
+    ///  * `group_name`: The name of the group, which is also used as its heading. Must be unique across all groups.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_opt_group(&mut self, group_name: &str) -> &mut ArgParser {
+        // Check if the group conflicts
+        if self.opt_groups.iter().any(|g| g.eq(group_name)) {
+            panic!("An option group with name '{}' already exists in this ArgParser instance.", group_name);
+        }
+
+        // Store the group internally
+        self.opt_groups.push(String::from(group_name));
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Moves the given option to the given group in the help string.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to move. Must refer to an already registered option.
+    ///  * `group_name`: The name of the group to move it to. Must refer to an already registered group.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_opt_group(&mut self, uid: &str, group_name: &str) -> &mut ArgParser {
+        // Make sure the group exists
+        if !self.opt_groups.iter().any(|g| g.eq(group_name)) {
+            panic!("Cannot move option '{}' to unknown option group '{}'.", uid, group_name);
+        }
+
+        // Try to find the option
+        match self.options.iter_mut().find(|o| o.uid.eq(uid)) {
+            Some(opt) => { opt.group = String::from(group_name); },
+            None      => { panic!("Cannot move unknown option '{}' to option group '{}'.", uid, group_name); }
+        }
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a group of options that are mutually exclusive, i.e., of which at most one may be given.
+    /// 
+    /// **Arguments**
+    ///  * `uids`: The uids of the options in the group. Must all refer to already registered options.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_mutex_group(&mut self, uids: Vec<String>) -> &mut ArgParser {
+        // Check if all uids exist
+        for uid in uids.iter() {
+            if !self.options.iter().any(|o| o.uid.eq(uid)) {
+                panic!("Cannot add unknown option '{}' to a mutually exclusive group.", uid);
+            }
+        }
 
-            // After this word, print the needed stuff
-            if separator.eq(" ") && *x + 1 + 1 < line_width {
-                // Simply print the space
-                result.push(' ');
-                *x += 1;
-            } else if separator.eq("\n") {
-                // Print a newline
-                result.reserve(1 + indent_width);
-                result.push('\n');
-                result.push_str(indent.as_str());
+        // Store the group internally
+        self.mutex_groups.push(uids);
 
-                // Reset the x
-                *x = indent_width;
-            } else if separator.eq("\r") {
-                // Ignore
-                continue;
-            } else if separator.eq("\t") {
-                // Print enough spaces to get to the next boundry of four
-                let target_x = *x - (*x % 4) + 4;
-                // Don't do it if we go too large
-                if target_x + 1 >= line_width { continue; }
-                // Print the spaces
-                result.push_str(ArgParser::generate_spaces(target_x - *x).as_str());
-                *x = target_x;
-            } else if separator.eq("\0") {
-                // Stop
-                break;
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a group of options of which exactly one must be given, e.g., to choose one output format.
+    /// 
+    /// If none of them is given, an error is given listing all of them. If more than one is given, the same error is given as for a mutually exclusive group.
+    /// 
+    /// **Arguments**
+    ///  * `uids`: The uids of the options in the group. Must all refer to already registered options.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_required_one_of(&mut self, uids: Vec<String>) -> &mut ArgParser {
+        // Check if all uids exist
+        for uid in uids.iter() {
+            if !self.options.iter().any(|o| o.uid.eq(uid)) {
+                panic!("Cannot add unknown option '{}' to a required group.", uid);
             }
         }
+
+        // Store the group internally
+        self.required_groups.push(uids);
+
+        // Return ourselves for chaining
+        return self;
     }
 
-    /// Helper function that prints the given positional to the given string, neatly formatted and line-wrapped.  
-    /// Note that the string will be assuming it is written after a newline, and will terminate itself with newlines too.
+    /// Registers that one option requires another, i.e., if the first is given, then so must the second.
     /// 
-    /// Note that this function will panic! is the given uid doesn't exists.
-    ///
     /// **Arguments**
-    ///  * `result`: The resulting string to write to.
-    ///  * `uid': The uid of the positional to write its help string for.
-    ///  * `indent_width`: The prefix width of each new line. Also the space positionals have before they interrupt the description column.
-    ///  * `line_width`: The total line width of each line.
-    fn print_pos_help(&self, result: &mut String, uid: &str, indent_width: usize, line_width: usize) {
-        // Try to find the positional
-        let mut opt_pos: std::option::Option<&Positional> = None;
-        for p in self.positionals.iter() {
-            if p.uid.eq(uid) {
-                opt_pos = Some(p);
-                break;
+    ///  * `uid`: The uid of the option that has the requirement. Must refer to an already registered option.
+    ///  * `requires`: The uid of the option that is required. Must refer to an already registered option.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_requires(&mut self, uid: &str, requires: &str) -> &mut ArgParser {
+        // Check if both uids exist
+        for u in [uid, requires] {
+            if !self.options.iter().any(|o| o.uid.eq(u)) {
+                panic!("Cannot add requirement for unknown option '{}'.", u);
             }
         }
-        if let None = opt_pos { panic!("Unknown positional '{}'.", uid); }
-        let pos = opt_pos.unwrap();
 
-        // Prepare the argument string and write it
-        let pos_name = format!("  <{}>", pos.name);
-        result.push_str(pos_name.as_str());
+        // Store the requirement internally
+        self.requirements.push((String::from(uid), String::from(requires)));
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers a new subcommand, which has its own parser.
+    /// 
+    /// If any subcommands are registered, the first positional given by the user is interpreted as the subcommand to run. All arguments after it are then parsed by the subcommand's parser instead.
+    /// 
+    /// ** Arguments **
+    ///  * `name`: The name of the subcommand as the user types it. Must be unique across all subcommands.
+    ///  * `parser`: The ArgParser that parses the arguments of the subcommand.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_subcommand(&mut self, name: &str, parser: ArgParser) -> &mut ArgParser {
+        // Check if the name conflicts
+        for (n, _) in self.subcommands.iter() {
+            if n.eq(name) {
+                panic!("A subcommand with name '{}' already exists in this ArgParser instance.", name);
+            }
+        }
+
+        // Store the subcommand internally
+        self.subcommands.push((String::from(name), parser));
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Removes all registered positionals, options and subcommands (including help and the double dash), as if the parser was just constructed.
+    /// 
+    /// Other settings (such as strict mode or the messages) are kept. Positionals registered afterwards start at index 0 again.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn clear(&mut self) -> &mut ArgParser {
+        // Remove the registrations and anything referring to them
+        self.positionals.clear();
+        self.options.clear();
+        self.subcommands.clear();
+        self.opt_groups.clear();
+        self.mutex_groups.clear();
+        self.required_groups.clear();
+        self.requirements.clear();
+
+        // Reset the special arguments
+        self.use_double_dash = false;
+        self.use_help = false;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Registers the double-dash that can be used to disable options
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_double_dash(&mut self) -> &mut ArgParser {
+        // Simply set that we use it
+        self.use_double_dash = true;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether option names (both shortnames and longnames) are matched case-insensitively.
+    /// 
+    /// Errors and help will still use the names as they were registered. By default, matching is case-sensitive.
+    /// 
+    /// **Arguments**
+    ///  * `case_insensitive`: Whether or not to match case-insensitively.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_case_insensitive(&mut self, case_insensitive: bool) -> &mut ArgParser {
+        // Simply store it
+        self.case_insensitive = case_insensitive;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether parsing stops at the first positional, after which all remaining arguments (including ones that look like options) are collected verbatim.
+    /// 
+    /// This is useful for wrapper commands that pass the rest of the arguments to another program. The collected arguments can be retrieved with `dict.get_trailing()`.
+    /// 
+    /// **Arguments**
+    ///  * `stop_at_first_pos`: Whether or not to stop at the first positional.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_stop_at_first_positional(&mut self, stop_at_first_pos: bool) -> &mut ArgParser {
+        // Simply store it
+        self.stop_at_first_pos = stop_at_first_pos;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether options that are given fewer values than their minimum take the positionals given after them as values instead.
+    /// 
+    /// For example, if '--pair' needs two values, then '--pair a -v b' gives it both 'a' and 'b' (instead of resulting in an error and 'b' as a positional). Positionals are only taken until the option has its minimum, and any options in between are parsed as usual. By default, this is disabled.
+    /// 
+    /// **Arguments**
+    ///  * `greedy_values`: Whether or not to fill up options with positionals.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_greedy_values(&mut self, greedy_values: bool) -> &mut ArgParser {
+        // Simply store it
+        self.greedy_values = greedy_values;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether giving help clears any errors and warnings that occurred during parsing.
+    /// 
+    /// By default, this is enabled, so that e.g. '--help --unknown' only results in help. If disabled, the dict still has help, but also keeps the errors and warnings, so they can be shown alongside it.
+    /// 
+    /// **Arguments**
+    ///  * `help_suppresses_errors`: Whether or not help clears the errors and warnings.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_help_suppresses_errors(&mut self, help_suppresses_errors: bool) -> &mut ArgParser {
+        // Simply store it
+        self.help_supp_errors = help_suppresses_errors;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets the placeholder for the options in the usage string, which is `[options]` by default.
+    /// 
+    /// **Arguments**
+    ///  * `label`: The placeholder to show instead, or 'none' to leave it out of the usage string altogether.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_usage_options_label(&mut self, label: std::option::Option<String>) -> &mut ArgParser {
+        // Simply store it
+        self.usage_opts_label = label;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets the templates used for the errors and warnings shown to the user, e.g., to translate them.
+    /// 
+    /// The errors themselves are unaffected; the templates are used by `dict.write_errors()` and `dict.print_errors()`, and for the warnings.
+    /// 
+    /// **Arguments**
+    ///  * `messages`: The new templates.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_messages(&mut self, messages: Messages) -> &mut ArgParser {
+        // Simply store them
+        self.messages = messages;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether the parser runs in strict mode, in which more positionals than defined results in an error instead of a warning.
+    /// 
+    /// By default, the parser is lenient and skips them with a warning.
+    /// 
+    /// **Arguments**
+    ///  * `strict`: Whether or not to run in strict mode.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_strict(&mut self, strict: bool) -> &mut ArgParser {
+        // Simply store it
+        self.strict = strict;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets a custom validator that is run after the built-in validation, e.g., for rules that span multiple options (such as '--start' having to come before '--end').
+    /// 
+    /// The validator gets the dict as parsed so far, and returns a message for each rule that is violated. These are added to the dict as errors (so, as usual, the dict is cleared if there are any). Replaces any validator set before.
+    /// 
+    /// **Arguments**
+    ///  * `validator`: The validator to run. Must be thread-safe, so that the ArgParser can be shared between threads.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_post_validator(&mut self, validator: Box<dyn Fn(&ArgDict) -> Vec<String> + Send + Sync>) -> &mut ArgParser {
+        // Store it in a way that it can be cloned
+        self.post_validator = Some(PostValidator(std::sync::Arc::from(validator)));
+
+        // Return ourselves for chaining
+        return self;
+    }
 
-        // Either pad the string until the description column, or add a newline
-        if 2 + pos_name.len() >= indent_width {
-            // Add a new line plus the indent
-            result.reserve(1 + indent_width);
-            result.push('\n');
-            result.push_str(ArgParser::generate_spaces(indent_width).as_str());
-        } else {
-            result.push_str(ArgParser::generate_spaces(indent_width - pos_name.len()).as_str());
+    /// Sets how many superfluous positionals are skipped with a warning, after which any more result in an error.
+    /// 
+    /// This is a middle ground between the default (lenient) behaviour and strict mode (see `set_strict()`), which takes precedence.
+    /// 
+    /// **Arguments**
+    ///  * `max_extra_positionals`: The number of superfluous positionals that are allowed.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_max_extra_positionals(&mut self, max_extra_positionals: usize) -> &mut ArgParser {
+        // Simply store it
+        self.max_extra_pos = Some(max_extra_positionals);
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets the characters that start an option, replacing the default dash.
+    /// 
+    /// A single prefix character starts a shortname (e.g., '/f'), and the same character twice starts a longname (e.g., '//flag'). Different prefixes may be mixed within the same invocation. Note that the help string and any errors still show options with dashes.
+    /// 
+    /// **Arguments**
+    ///  * `prefixes`: The list of characters that start an option. Cannot be empty.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_option_prefixes(&mut self, prefixes: Vec<char>) -> &mut ArgParser {
+        // Make sure there is at least one
+        if prefixes.len() == 0 {
+            panic!("Cannot set an empty list of option prefixes.");
         }
 
-        // Start writing the lines, linewrapped
-        let mut x: usize = indent_width;
-        ArgParser::print_description(result, &mut x, pos.description.as_str(), indent_width, line_width);
+        // Store them
+        self.opt_prefixes = prefixes;
 
-        // Write a final newline character and we're done
-        result.push('\n');
+        // Return ourselves for chaining
+        return self;
     }
 
-    /// Helper function that prints the given option to the given string, neatly formatted and line-wrapped.  
-    /// Note that the string will be assuming it is written after a newline, and will terminate itself with newlines too.
+    /// Disables interpreting arguments that start with '@' as response files, so they are parsed like any other argument.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn disable_response_files(&mut self) -> &mut ArgParser {
+        // Simply set that we don't use it
+        self.use_resp_files = false;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets whether parse() prints the help string to stdout itself when help is given.
+    /// 
+    /// By default, it doesn't, and it is up to the caller to check `dict.has_help()` and print `get_help()` as it sees fit.
+    /// 
+    /// **Arguments**
+    ///  * `auto_help`: Whether or not to print the help automatically.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_auto_help(&mut self, auto_help: bool) -> &mut ArgParser {
+        // Simply store it
+        self.auto_help = auto_help;
+
+        // Return ourselves for chaining
+        return self;
+    }
+
+    /// Sets the widths used by `get_help_default()` (and thus the automatically printed help), overriding `DEFAULT_INDENT_WIDTH` and the detected terminal width.
     /// 
-    /// Note that this function will panic! is the given uid doesn't exists.
-    ///
     /// **Arguments**
-    ///  * `result`: The resulting string to write to.
-    ///  * `uid': The uid of the option to write its help string for.
     ///  * `indent_width`: The prefix width of each new line. Also the space options have before they interrupt the description column.
     ///  * `line_width`: The total line width of each line.
-    fn print_opt_help(&self, result: &mut String, uid: &str, indent_width: usize, line_width: usize) {
-        // Try to find the positional
-        let mut opt_opt: std::option::Option<&Option> = None;
-        for o in self.options.iter() {
-            if o.uid.eq(uid) {
-                opt_opt = Some(o);
-                break;
-            }
-        }
-        if let None = opt_opt { panic!("Unknown option '{}'.", uid); }
-        let opt = opt_opt.unwrap();
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_help_widths(&mut self, indent_width: usize, line_width: usize) -> &mut ArgParser {
+        // Simply store them
+        self.help_widths = Some((indent_width, line_width));
 
-        // Prepare the argument string and write it
-        let opt_name = format!("  {}--{}{}", if opt.shortname.len() > 0 { format!("-{},", opt.shortname) } else { String::new() }, opt.longname, if opt.param_description.len() > 0 { format!(" {}", opt.param_description) } else { String::new() });
-        result.push_str(opt_name.as_str());
+        // Return ourselves for chaining
+        return self;
+    }
 
-        // Either pad the string until the description column, or add a newline
-        if 2 + opt_name.len() >= indent_width {
-            // Add a new line plus the indent
-            result.reserve(1 + indent_width);
-            result.push('\n');
-            result.push_str(ArgParser::generate_spaces(indent_width).as_str());
-        } else {
-            result.push_str(ArgParser::generate_spaces(indent_width - opt_name.len()).as_str());
-        }
+    /// Sets whether unknown options are collected instead of resulting in errors, e.g., for wrappers that pass them on to another command.
+    /// 
+    /// The unknown options can be retrieved with `dict.get_unknown()`, and parsing continues as if they weren't given. Since there is no telling how many values an unknown option takes, any values given separately from it (e.g., '--unknown value') are collected with it up to the next option or double dash; give positionals before unknown options (or after the double dash) to keep them apart.
+    /// 
+    /// **Arguments**
+    ///  * `collect_unknown`: Whether or not to collect unknown options.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_collect_unknown(&mut self, collect_unknown: bool) -> &mut ArgParser {
+        // Simply store it
+        self.collect_unknown = collect_unknown;
 
-        // Start writing the lines, linewrapped
-        let mut x: usize = indent_width;
-        ArgParser::print_description(result, &mut x, opt.description.as_str(), indent_width, line_width);
+        // Return ourselves for chaining
+        return self;
+    }
 
-        // Write a final newline character and we're done
-        result.push('\n');
+    /// Sets whether the defaults and choices of positionals and options are appended to their descriptions in the help string, e.g., '(default: a) (choices: a, b)'.
+    /// 
+    /// By default, this is disabled, and choices are listed on their own line instead (while defaults are not shown at all).
+    /// 
+    /// **Arguments**
+    ///  * `annotate_help`: Whether or not to append the defaults and choices.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_annotate_help(&mut self, annotate_help: bool) -> &mut ArgParser {
+        // Simply store it
+        self.annotate_help = annotate_help;
+
+        // Return ourselves for chaining
+        return self;
     }
 
+    /// Sets whether the help string is written without the blank lines before and after it, e.g., to concatenate it with other text.
+    /// 
+    /// If enabled, the help string starts immediately with the usage string and ends with a single newline. The blank lines between its sections are kept either way.
+    /// 
+    /// **Arguments**
+    ///  * `help_trim`: Whether or not to leave out the leading and trailing blank lines.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_help_trim(&mut self, help_trim: bool) -> &mut ArgParser {
+        // Simply store it
+        self.help_trim = help_trim;
 
+        // Return ourselves for chaining
+        return self;
+    }
 
-    /// Registers a new positional argument.
+    /// Sets the brackets around the names of positionals in the usage and help strings. Defaults to angle brackets (e.g., `<file>`).
+    /// 
+    /// Optional positionals are additionally enclosed in square brackets in the usage string, except if the square bracket style is used.
     /// 
     /// **Arguments**
-    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with options, so go nuts.
-    ///  * `name`: Readable name for use in the usage/help string.
-    ///  * `description`: A string description of the positional.
-    pub fn add_pos(&mut self, uid: &str, name: &str, description: &str) {
-        // Check if the uid conflicts
-        for pos in self.positionals.iter() {
-            if pos.uid == uid {
-                panic!("A positional with uid '{}' already exists in this ArgParser instance.", uid);
-            }
-        }
+    ///  * `style`: The brackets to use.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_pos_bracket_style(&mut self, style: BracketStyle) -> &mut ArgParser {
+        // Simply store it
+        self.pos_brackets = style;
 
-        // Create a new Positional argument
-        let result = Positional {
-            uid: String::from(uid),
-            index: self.positionals.len(),
-            name: String::from(name),
-            description: String::from(description)
-        };
+        // Return ourselves for chaining
+        return self;
+    }
 
-        // Store the positional internally
-        self.positionals.push(result);
+    /// Sets the graphemes that separate words in descriptions in the help string, i.e., where lines may be broken. Defaults to whitespace (space, `\n`, `\t` and `\r`).
+    /// 
+    /// Whitespace separators keep their special meaning (e.g., a newline always starts a new line), while any others are written as-is (e.g., giving '/' allows breaking long paths after each slash). Anything that isn't a separator, such as a non-breaking space, is never broken on.
+    /// 
+    /// **Arguments**
+    ///  * `separators`: The new separators.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_word_separators(&mut self, separators: Vec<String>) -> &mut ArgParser {
+        // Simply store them
+        self.word_separators = separators;
+
+        // Return ourselves for chaining
+        return self;
     }
 
-    /// Registers a new option.
+    /// Sets whether the indent width of the help string is computed automatically, overriding any indent width given.
     /// 
-    /// ** Arguments **
-    ///  * `uid`: Unique identifier for this argument. Doesn't share the names with positionals, so go nuts.
-    ///  * `shortname`: A single character, optional identifier for the option. Must be unique across all options. If you don't want to use it, pass a new/empty string.
-    ///  * `longname`: A multi-character identifier for the option. Must be unique across all options.
-    ///  * `min_n_values`: The minimum number of values for this option. If it's a flag, pass no argument (0).
-    ///  * `max_n_values`: The maximum number of values for this option. If it's a flag, pass no argument (0). Cannot be smaller than `min_n_values`.
-    ///  * `param_description`: A string description of the parameters of this option. Will most likely be a list of types or something.
-    ///  * `description`: A string description of the option.
-    pub fn add_opt(&mut self, uid: &str, shortname: &str, longname: &str, min_n_values: usize, max_n_values: usize, param_description: &str, description: &str) {
-        // Check if the shortname is valid
-        let oshortname = OpString::new(shortname);
-        if oshortname.len() > 1 {
-            panic!("A shortlabel cannot have more than one character: {} > 1.", shortname.len());
-        }
+    /// If enabled, the indent width is the narrowest one for which all positionals and options (including their parameters) fit before their descriptions, up to a maximum of 40. Longer ones are still put on their own line.
+    /// 
+    /// **Arguments**
+    ///  * `auto_indent`: Whether or not to compute the indent width.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_auto_indent(&mut self, auto_indent: bool) -> &mut ArgParser {
+        // Simply store it
+        self.auto_indent = auto_indent;
 
-        // Check if the uid, shortname or longnames are in conflict
-        for opt in self.options.iter() {
-            if opt.uid.eq(uid) {
-                panic!("An option with uid '{}' already exists in this ArgParser instance.", uid);
-            }
-            if shortname.len() > 0 && opt.shortname.eq(shortname) {
-                panic!("An option with shortlabel '{}' already exists in this ArgParser instance.", shortname);
-            }
-            if opt.longname.eq(longname) {
-                panic!("An option with longname '{}' already exists in this ArgParser instance.", longname);
-            }
-        }
+        // Return ourselves for chaining
+        return self;
+    }
 
-        // Make sure the max_n_values isn't smaller
-        if max_n_values < min_n_values {
-            panic!("max_n_values has to be equal to or larger than min_n_values; {} > {}", max_n_values, min_n_values);
+    /// Sets the distance between tab stops used when a description in the help string contains tabs. Defaults to 4.
+    /// 
+    /// **Arguments**
+    ///  * `tab_width`: The distance between tab stops. Cannot be 0.
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn set_tab_width(&mut self, tab_width: usize) -> &mut ArgParser {
+        // Make sure it's valid
+        if tab_width == 0 {
+            panic!("Cannot set a tab width of 0.");
         }
 
-        // Create a new Option
-        let result = Option {
-            uid               : String::from(uid),
-            shortname         : String::from(shortname),
-            longname          : String::from(longname),
-            min_n_values,
-            max_n_values,
-            param_description : String::from(param_description),
-            description       : String::from(description)
-        };
-
-        // Store the option intenally
-        self.options.push(result);
-    }
+        // Store it
+        self.tab_width = tab_width;
 
-    /// Registers the double-dash that can be used to disable options
-    pub fn add_double_dash(&mut self) {
-        // Simply set that we use it
-        self.use_double_dash = true;
+        // Return ourselves for chaining
+        return self;
     }
 
     /// Registers a help-flag as '-h' and '--help'.
     /// 
-    /// To check if it was specified, call 'dict.has_opt(parse_args::HELP_UID)' on the resulting dict after the parse() call.
+    /// To check if it was specified, call 'dict.has_help()' on the resulting dict after the parse() call. Note that the help string is not printed automatically unless `set_auto_help()` is used.
     /// 
     /// If run, reserves the '-h' and '--help' flags for standard help usage. Doing it this way automatically enables parsing help before anything else is parsed.
-    pub fn add_help(&mut self) {
+    /// 
+    /// **Returns**  
+    /// A mutable reference to this ArgParser, so calls can be chained.
+    pub fn add_help(&mut self) -> &mut ArgParser {
         // Check if the uid, shortname or longnames are in conflict
-        for opt in self.options.iter() {
-            if opt.uid.eq(HELP_UID) {
-                panic!("Cannot add help, as an option with uid '{}' already exists in this ArgParser instance.", HELP_UID);
-            }
-            if HELP_SHORTNAME.len() > 0 && opt.shortname.eq(HELP_SHORTNAME) {
-                panic!("Cannot add help, as an option with shortlabel '{}' already exists in this ArgParser instance.", HELP_SHORTNAME);
-            }
-            if opt.longname.eq(HELP_LONGNAME) {
-                panic!("Cannot add help, as an option with longname '{}' already exists in this ArgParser instance.", HELP_LONGNAME);
-            }
+        if let Err(err) = self.check_conflicts(Some(HELP_UID), HELP_SHORTNAME, HELP_LONGNAME, false) {
+            panic!("Cannot add help: {}", err);
         }
 
         // Create the option
@@ -734,7 +5612,27 @@ impl ArgParser {
             min_n_values      : 0,
             max_n_values      : 0,
             param_description : String::new(),
-            description       : String::from(HELP_DESCRIPTION)
+            description       : String::from(HELP_DESCRIPTION),
+            env_var           : String::new(),
+            negatable         : false,
+            required          : false,
+            choices           : Vec::new(),
+            counted           : false,
+            map               : false,
+            repeatable        : false,
+            group             : String::new(),
+            aliases           : Vec::new(),
+            short_aliases     : Vec::new(),
+            value_delimiter   : None,
+            keep_empty_values : false,
+            value_terminator  : None,
+            allow_dash_values : false,
+            int_range         : None,
+            require_if_min    : false,
+            from_file         : None,
+            hidden            : false,
+            rest              : false,
+            default_value     : None,
         };
 
         // Store the option, but at the start of the vector
@@ -742,6 +5640,9 @@ impl ArgParser {
 
         // Also note the help is defined as special
         self.use_help = true;
+
+        // Return ourselves for chaining
+        return self;
     }
 
 
@@ -810,6 +5711,63 @@ impl ArgParser {
         panic!("Cannot get longname of unknown option '{}'.", uid);
     }
 
+    /// Returns the description of the option with the given name, e.g., to answer "what does '--foo' do?".
+    /// 
+    /// **Arguments**
+    ///  * `name`: The uid, longname or shortname of the option (or any of its aliases). The names may be given with or without their prefix (e.g., both '--foo' and 'foo' work).
+    /// 
+    /// **Returns**  
+    /// The description of the option, or None if no option has that uid or name. If it matches both the uid of one option and the name of another, the option with that uid is returned.
+    pub fn describe_opt(&self, name: &str) -> std::option::Option<String> {
+        // Try the uids first, which are matched exactly
+        if let Some(opt) = self.options.iter().find(|o| o.uid.eq(name)) {
+            return Some(opt.description.clone());
+        }
+
+        // Otherwise, try the names without their prefixes
+        let name = name.trim_start_matches(|c| self.opt_prefixes.contains(&c));
+        if name.len() == 0 { return None; }
+        return self.options.iter()
+            .find(|o| self.names_eq(&o.longname, name) || o.aliases.iter().any(|a| self.names_eq(a, name)) || self.names_eq(&o.shortname, name) || o.short_aliases.iter().any(|a| self.names_eq(a, name)))
+            .map(|o| o.description.clone());
+    }
+
+    /// Returns the definitions of all registered positionals, e.g., to generate shell completions.
+    /// 
+    /// **Returns**  
+    /// A list with a read-only view of each positional, in order of their indices.
+    pub fn pos_defs(&self) -> Vec<PositionalInfo<'_>> {
+        self.positionals.iter().map(|p| PositionalInfo {
+            uid         : &p.uid,
+            index       : p.index,
+            name        : &p.name,
+            description : &p.description,
+            multi       : p.multi,
+            optional    : p.optional,
+            hidden      : p.hidden,
+        }).collect()
+    }
+
+    /// Returns the definitions of all registered options (including help, if registered), e.g., to generate shell completions.
+    /// 
+    /// **Returns**  
+    /// A list with a read-only view of each option, in the order they are listed in the help string.
+    pub fn opt_defs(&self) -> Vec<OptionInfo<'_>> {
+        self.options.iter().map(|o| OptionInfo {
+            uid               : &o.uid,
+            shortname         : &o.shortname,
+            longname          : &o.longname,
+            aliases           : &o.aliases,
+            short_aliases     : &o.short_aliases,
+            min_n_values      : o.min_n_values,
+            max_n_values      : o.max_n_values,
+            param_description : &o.param_description,
+            description       : &o.description,
+            required          : o.required,
+            hidden            : o.hidden,
+        }).collect()
+    }
+
 
 
     /// Generates the usage string for this argument instance.
@@ -829,15 +5787,148 @@ impl ArgParser {
         result.push_str(exec_name);
 
         // Add the options placeholder
-        if self.options.len() > 0 { result.push_str(" [options]"); }
+        if self.options.iter().any(|o| !o.hidden) {
+            if let Some(label) = &self.usage_opts_label { result.push_str(&format!(" {}", label)); }
+        }
 
         // Add the positionals
-        for pos in self.positionals.iter() {
-            result.push_str(format!(" <{}>", pos.name).as_str());
+        for pos in self.positionals.iter().filter(|p| !p.hidden) {
+            if pos.optional && self.pos_brackets != BracketStyle::Square {
+                result.push_str(format!(" [{}]", self.pos_name(pos)).as_str());
+            } else {
+                result.push_str(format!(" {}", self.pos_name(pos)).as_str());
+            }
+        }
+
+        // Add the subcommands
+        if self.subcommands.len() > 0 {
+            let names: Vec<&str> = self.subcommands.iter().map(|(n, _)| n.as_str()).collect();
+            result.push_str(format!(" <{}> ...", names.join("|")).as_str());
+        }
+
+        // Return it!
+        return result;
+    }
+
+    /// Writes the help string for this argument instance to the given writer.
+    /// 
+    /// **Arguments**
+    ///  * `w`: The writer to write the help string to.
+    ///  * `exec_name`: The name of the executable.
+    ///  * `indent_width`: The prefix width of each new line. Also the space options have before they interrupt the description column. A good default is `DEFAULT_INDENT_WIDTH` (20).
+    ///  * `line_width`: The total line width of each line. A good default is `DEFAULT_LINE_WIDTH` (80).
+    /// **Returns**  
+    /// Nothing if the help was written successfully, or the error that occurred while writing otherwise.
+    pub fn write_help<W: Write>(&self, w: &mut W, exec_name: &str, indent_width: usize, line_width: usize) -> io::Result<()> {
+        // Compute the indent width instead, if told to do so
+        let indent_width = if self.auto_indent { self.auto_indent_width() } else { indent_width };
+
+        // Clamp the widths so that there is always room for the descriptions, even on tiny terminals
+        let line_width   = line_width.max(2);
+        let indent_width = if indent_width >= line_width { line_width / 2 } else { indent_width };
+
+        // Create a new string
+        let mut result: String = String::new();
+
+        // Print the usage string (preceded by a blank line, unless trimmed)
+        if !self.help_trim { result.push_str("\n"); }
+        result.push_str(format!("{}\n", self.get_usage(exec_name).as_str()).as_str());
+        result.push_str("\n\n");
+
+        // Print the positionals
+        result.push_str("Positionals:\n");
+        if self.positionals.iter().any(|p| !p.hidden) {
+            for p in self.positionals.iter().filter(|p| !p.hidden) {
+                // Print it
+                self.print_pos_help(&mut result, &p.uid, indent_width, line_width);
+            }
+        } else {
+            result.push_str("   <none>\n");
+        }
+
+        // Print the options without a group (skipping the section if all options are grouped)
+        if self.opt_groups.len() == 0 || self.options.iter().any(|o| !o.hidden && o.group.len() == 0) {
+            result.push_str("\nOptions:\n");
+            if self.options.iter().any(|o| !o.hidden) {
+                for o in self.options.iter().filter(|o| !o.hidden && o.group.len() == 0) {
+                    // Print it
+                    self.print_opt_help(&mut result, &o.uid, indent_width, line_width);
+                }
+            } else {
+                result.push_str("   <none>\n");
+            }
+        }
+
+        // Print the options in each group
+        for group in self.opt_groups.iter() {
+            result.push_str(format!("\n{}:\n", group).as_str());
+            if self.options.iter().any(|o| !o.hidden && o.group.eq(group)) {
+                for o in self.options.iter().filter(|o| !o.hidden && o.group.eq(group)) {
+                    // Print it
+                    self.print_opt_help(&mut result, &o.uid, indent_width, line_width);
+                }
+            } else {
+                result.push_str("   <none>\n");
+            }
         }
+        if !self.help_trim { result.push('\n'); }
+
+        // Done, write it
+        return w.write_all(result.as_bytes());
+    }
+
+    /// Generates the help string for this argument instance, using the widths set with `set_help_widths()`.
+    /// 
+    /// If no widths are set, uses an indent width of `DEFAULT_INDENT_WIDTH` and the terminal width as line width. The latter is read from the 'COLUMNS' environment variable, falling back to `DEFAULT_LINE_WIDTH` if it's not set (or not a number).
+    /// 
+    /// **Arguments**
+    ///  * `exec_name`: The name of the executable.
+    /// **Returns**  
+    /// A string with the help for this instance.
+    #[inline]
+    pub fn get_help_default(&self, exec_name: &str) -> String {
+        self.get_help_for_columns(exec_name, std::env::var("COLUMNS").ok().as_deref())
+    }
+
+    /// Implements `get_help_default()` for the given terminal width, so that it doesn't have to be read from the environment.
+    /// 
+    /// **Arguments**
+    ///  * `exec_name`: The name of the executable.
+    ///  * `columns`: The value of the 'COLUMNS' environment variable, if it is set.
+    /// **Returns**  
+    /// A string with the help for this instance.
+    fn get_help_for_columns(&self, exec_name: &str, columns: std::option::Option<&str>) -> String {
+        // Resolve the widths, then generate the help with those
+        let (indent_width, line_width) = self.resolve_help_widths(columns);
+        return self.get_help(exec_name, indent_width, line_width);
+    }
+
+    /// Writes the help string that is shown by `parse()` if help is given, but only if `set_auto_help()` is used. Writes nothing otherwise.
+    /// 
+    /// **Arguments**
+    ///  * `w`: The writer to write the help string to (i.e., stdout when parsing).
+    ///  * `exec_name`: The name of the executable.
+    ///  * `columns`: The value of the 'COLUMNS' environment variable, if it is set.
+    /// 
+    /// **Returns**  
+    /// Nothing if the help was written successfully (or didn't have to be), or the error that occurred while writing otherwise.
+    fn write_auto_help<W: Write>(&self, w: &mut W, exec_name: &str, columns: std::option::Option<&str>) -> io::Result<()> {
+        if !self.auto_help { return Ok(()); }
+        return w.write_all(self.get_help_for_columns(exec_name, columns).as_bytes());
+    }
 
-        // Return it!
-        return result;
+    /// Helper function that resolves the widths used by `get_help_default()`.
+    /// 
+    /// **Arguments**
+    ///  * `columns`: The value of the 'COLUMNS' environment variable, if it is set.
+    /// 
+    /// **Returns**  
+    /// The indent and line width, which are the ones set with `set_help_widths()` or else the default indent width and the given terminal width (or the default line width if that's not a number).
+    fn resolve_help_widths(&self, columns: std::option::Option<&str>) -> (usize, usize) {
+        return match self.help_widths {
+            Some(widths) => widths,
+            None         => (DEFAULT_INDENT_WIDTH, columns.and_then(|c| c.trim().parse::<usize>().ok()).unwrap_or(DEFAULT_LINE_WIDTH)),
+        };
     }
 
     /// Generates the help string for this argument instance.
@@ -846,43 +5937,133 @@ impl ArgParser {
     /// 
     /// **Arguments**
     ///  * `exec_name`: The name of the executable.
-    ///  * `indent_width`: The prefix width of each new line. Also the space options have before they interrupt the description column. A good default is `20`.
-    ///  * `line_width`: The total line width of each line. A good default is 80.
+    ///  * `indent_width`: The prefix width of each new line. Also the space options have before they interrupt the description column. A good default is `DEFAULT_INDENT_WIDTH` (20).
+    ///  * `line_width`: The total line width of each line. A good default is `DEFAULT_LINE_WIDTH` (80).
     /// **Returns**  
     /// A string with the help for this instance.
     pub fn get_help(&self, exec_name: &str, indent_width: usize, line_width: usize) -> String {
-        // Create a new string
-        let mut result: String = String::new();
+        // Write the help to a buffer
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_help(&mut buffer, exec_name, indent_width, line_width).unwrap();
 
-        // Print the usage string
-        result.push_str("\n");
-        result.push_str(format!("{}\n", self.get_usage(exec_name).as_str()).as_str());
-        result.push_str("\n\n");
+        // Convert it to a string, which cannot fail as we wrote it from one
+        return String::from_utf8(buffer).unwrap();
+    }
 
-        // Print the positionals
-        result.push_str("Positionals:\n");
-        if self.positionals.len() > 0 {
-            for p in self.positionals.iter() {
-                // Print it
-                self.print_pos_help(&mut result, &p.uid, indent_width, line_width);
-            }
-        } else {
-            result.push_str("   <none>\n");
+    /// Generates a compact summary of the options of this instance, e.g., for a '--help-short' option.
+    /// 
+    /// Unlike `get_help()`, this has no usage string or positionals, and is not line-wrapped: each option is listed on a single line with its longname and the first line of its description.
+    /// 
+    /// **Arguments**
+    ///  * `exec_name`: The name of the executable, which is used as a heading.
+    /// 
+    /// **Returns**  
+    /// A string with the summary, with a trailing newline.
+    pub fn get_summary(&self, exec_name: &str) -> String {
+        // Find the longest longname (in graphemes, not bytes) so the descriptions line up
+        let width: usize = self.options.iter().filter(|o| !o.hidden).map(|o| OpString::new(&o.longname).len()).max().unwrap_or(0);
+
+        // Write each option on its own line
+        let mut result: String = format!("{} options:\n", exec_name);
+        for o in self.options.iter().filter(|o| !o.hidden) {
+            let description: &str = o.description.lines().next().unwrap_or("");
+            result.push_str(&format!("  --{}{}  {}\n", o.longname, ArgParser::generate_spaces(width - OpString::new(&o.longname).len()), description));
         }
 
-        // Print the options
-        result.push_str("\nOptions:\n");
-        if self.options.len() > 0 {
-            for o in self.options.iter() {
-                // Print it
-                self.print_opt_help(&mut result, &o.uid, indent_width, line_width);
+        // Done
+        return result;
+    }
+
+    /// Generates a bash completion script for this instance, which completes the longnames of all options (including any aliases, negated flags and help) and the subcommands.
+    /// 
+    /// The script defines a function called '_<exec_name>' and registers it with `complete`. Users can source it from their shell or put it in a completion file.
+    /// 
+    /// **Arguments**
+    ///  * `exec_name`: The name of the executable to complete. Any path before it is ignored.
+    /// 
+    /// **Returns**  
+    /// A string with the completion script.
+    pub fn generate_bash_completion(&self, exec_name: &str) -> String {
+        // Get the name of the executable without its path, and a version of it that can be used in a function name
+        let exec_name = match exec_name.rfind('/') { Some(pos) => &exec_name[pos + 1..], None => exec_name };
+        let func_name: String = exec_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+
+        // Collect all words to complete
+        let mut words: Vec<String> = Vec::new();
+        for o in self.options.iter().filter(|o| !o.hidden) {
+            words.push(format!("--{}", o.longname));
+            if o.negatable { words.push(format!("--no-{}", o.longname)); }
+            for alias in o.aliases.iter() {
+                words.push(format!("--{}", alias));
             }
-        } else {
-            result.push_str("   <none>\n");
         }
-        result.push('\n');
+        for (name, _) in self.subcommands.iter() {
+            words.push(name.clone());
+        }
+
+        // Write the script
+        let mut result = String::new();
+        result.push_str(&format!("_{}() {{\n", func_name));
+        result.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+        result.push_str(&format!("    COMPREPLY=( $(compgen -W \"{}\" -- \"${{cur}}\") )\n", words.join(" ")));
+        result.push_str("}\n");
+        result.push_str(&format!("complete -F _{} {}\n", func_name, exec_name));
+
+        // Done
+        return result;
+    }
+
+    /// Generates a machine-readable description of this instance as JSON, e.g., for IDE integrations.
+    /// 
+    /// The result is a single object with the fields 'positionals' and 'options', which list the same fields as `pos_defs()` and `opt_defs()` (in the same order), and 'subcommands', which lists the names of the subcommands. Hidden positionals and options are included, but marked as such.
+    /// 
+    /// **Returns**  
+    /// A string with the JSON, on a single line.
+    pub fn get_help_json(&self) -> String {
+        // Write the positionals
+        let positionals: Vec<String> = self.pos_defs().iter().map(|p| format!(
+            "{{\"uid\":{},\"index\":{},\"name\":{},\"description\":{},\"multi\":{},\"optional\":{},\"hidden\":{}}}",
+            ArgParser::json_string(p.uid), p.index, ArgParser::json_string(p.name), ArgParser::json_string(p.description), p.multi, p.optional, p.hidden,
+        )).collect();
+
+        // Write the options
+        let options: Vec<String> = self.opt_defs().iter().map(|o| format!(
+            "{{\"uid\":{},\"shortname\":{},\"longname\":{},\"aliases\":[{}],\"short_aliases\":[{}],\"min_n_values\":{},\"max_n_values\":{},\"param_description\":{},\"description\":{},\"required\":{},\"hidden\":{}}}",
+            ArgParser::json_string(o.uid), ArgParser::json_string(o.shortname), ArgParser::json_string(o.longname),
+            o.aliases.iter().map(|a| ArgParser::json_string(a)).collect::<Vec<String>>().join(","),
+            o.short_aliases.iter().map(|a| ArgParser::json_string(a)).collect::<Vec<String>>().join(","),
+            o.min_n_values, o.max_n_values, ArgParser::json_string(o.param_description), ArgParser::json_string(o.description), o.required, o.hidden,
+        )).collect();
+
+        // Write the subcommands
+        let subcommands: Vec<String> = self.subcommands.iter().map(|(name, _)| ArgParser::json_string(name)).collect();
+
+        // Put it together
+        return format!("{{\"positionals\":[{}],\"options\":[{}],\"subcommands\":[{}]}}", positionals.join(","), options.join(","), subcommands.join(","));
+    }
 
-        // Done!
+    /// Helper function that writes the given string as a JSON string, i.e., quoted and with any special characters escaped.
+    /// 
+    /// **Arguments**
+    ///  * `s`: The string to write.
+    /// 
+    /// **Returns**  
+    /// The JSON string, including its quotes.
+    fn json_string(s: &str) -> String {
+        let mut result = String::with_capacity(s.len() + 2);
+        result.push('"');
+        for c in s.chars() {
+            match c {
+                '"'  => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+                c    => result.push(c),
+            }
+        }
+        result.push('"');
         return result;
     }
 
@@ -890,97 +6071,244 @@ impl ArgParser {
 
     /// Tries to parse the internally defined positionals and arguments according to the given list of arguments.
     /// 
+    /// Unless disabled with `disable_response_files()`, any argument that starts with '@' is first replaced by the whitespace-separated contents of the file it names.
+    /// 
+    /// Option values take precedence over positionals while there are positionals left to fill: an option consumes the non-option arguments following it until it has `max_n_values` values, regardless of how many positionals are still unfilled. Only the arguments that are not consumed this way are assigned to positionals, in order. However, once more positionals were given than are registered (and none of them is variadic), options no longer take values given separately from them, and every non-option argument after that is skipped with a warning instead.
+    /// 
+    /// Empty arguments are taken as (empty) values by options that still accept values, and are skipped otherwise.
+    /// 
     /// ** Arguments **
     ///  * `args`: The list of arguments, as a vector of str's.
     /// 
     /// ** Returns **
-    /// An ArgDict with the results. If any errors occurred, parses no errors and adds the relevant errors to the dict. If help is given and the user gave it too, only that option is present in the ArgDict.
+    /// An ArgDict with the results. If any errors occurred, parses no errors and adds the relevant errors to the dict. If help is given and the user gave it too, only that option is present in the ArgDict (and nothing is printed unless `set_auto_help()` is used).
+    #[inline]
     pub fn parse(&self, args: &Vec<String>) -> ArgDict {
+        self.parse_traced(args, &mut Vec::new(), &mut None)
+    }
+
+    /// Parses the arguments given to the program by the environment, i.e., `std::env::args()`.
+    /// 
+    /// **Returns**  
+    /// An ArgDict with the results, as described by `parse()`.
+    #[inline]
+    pub fn parse_env(&self) -> ArgDict {
+        self.parse(&get_args_from_env!())
+    }
+
+    /// Parses the given list of OS strings like `parse()`, e.g., the ones from `std::env::args_os()`.
+    /// 
+    /// Note that the conversion is lossy: any argument that is not valid UTF-8 is converted with `OsStr::to_string_lossy()` first, replacing the invalid bytes with U+FFFD. As such, the resulting values may not refer to the same paths as the original arguments. Valid arguments are parsed exactly as they would be by `parse()`.
+    /// 
+    /// **Arguments**
+    ///  * `args`: The list of arguments, as a vector of OsStrings.
+    /// 
+    /// **Returns**  
+    /// An ArgDict with the results, as described by `parse()`.
+    pub fn parse_os(&self, args: &Vec<std::ffi::OsString>) -> ArgDict {
+        // Convert the arguments to normal strings
+        let args: Vec<String> = args.iter().map(|a| a.to_string_lossy().into_owned()).collect();
+
+        // Parse those
+        return self.parse(&args);
+    }
+
+    /// Parses the given list of arguments like `parse()`, but instead of the results, returns how each argument was classified. Useful for debugging why an argument wasn't parsed as expected.
+    /// 
+    /// The classifications are one of:
+    ///  * `option:<name>`: The argument is the option with the given name (e.g., '--opt1' or '-o'), possibly with an inline value.
+    ///  * `value-of:<name>`: The argument is a value of the option with the given name.
+    ///  * `positional:<uid>`: The argument is assigned to the positional with the given uid.
+    ///  * `subcommand:<name>`: The argument is the subcommand with the given name. The arguments after it are classified by the subcommand's parser.
+    ///  * `separator`: The argument is the double dash.
+    ///  * `terminator`: The argument ends the values of an option (see `set_value_terminator()`).
+    ///  * `trailing`: The argument is passed through verbatim (see `set_stop_at_first_positional()`).
+    ///  * `unknown`: The argument is an unknown option or subcommand.
+    ///  * `invalid`: The argument is an option that was given a value it doesn't accept.
+    ///  * `skipped`: The argument is skipped, e.g., because it is empty or there are more positionals than defined.
+    /// 
+    /// ** Arguments **
+    ///  * `args`: The list of arguments, as a vector of str's.
+    /// 
+    /// ** Returns **
+    /// A list of pairs of each argument (after expanding response files, but without the executable) and its classification, in order.
+    pub fn parse_explain(&self, args: &Vec<String>) -> Vec<(String, String)> {
+        // Simply parse, and only keep the trace
+        let mut trace: Vec<(String, String)> = Vec::new();
+        self.parse_traced(args, &mut trace, &mut None);
+        return trace;
+    }
+
+    /// Parses the given list of arguments like `parse()`, but stops at the first argument it can't classify (i.e., an unknown option or a positional for which there is no positional left) instead of reporting it.
+    /// 
+    /// This is useful to delegate the remaining arguments to another parser. No error or warning is added for the argument it stops at, and unknown options are not collected even if `set_collect_unknown()` is used. Note that any other errors (e.g., missing required options) are still reported as usual.
+    /// 
+    /// ** Arguments **
+    ///  * `args`: The list of arguments, as a vector of str's.
+    /// 
+    /// ** Returns **
+    /// An ArgDict with the results of the arguments before the one it stopped at, as described by `parse()`, and the arguments from the one it stopped at onwards, exactly as given. The latter is empty if all arguments were parsed.
+    pub fn parse_partial(&self, args: &Vec<String>) -> (ArgDict, Vec<String>) {
+        // Parse with a place to leave the remainder
+        let mut remainder: std::option::Option<Vec<String>> = Some(Vec::new());
+        let result = self.parse_traced(args, &mut Vec::new(), &mut remainder);
+        return (result, remainder.unwrap_or_default());
+    }
+
+    /// Implements `parse()`, `parse_explain()` and `parse_partial()`.
+    /// 
+    /// ** Arguments **
+    ///  * `args`: The list of arguments, as a vector of str's.
+    ///  * `trace`: The list to add each argument to, paired with how it was classified.
+    ///  * `remainder`: If given, parsing stops at the first unknown option or superfluous positional, and the arguments from there on are stored in it.
+    /// 
+    /// ** Returns **
+    /// An ArgDict with the results, as described by `parse()`.
+    fn parse_traced(&self, args: &Vec<String>, trace: &mut Vec<(String, String)>, remainder: &mut std::option::Option<Vec<String>>) -> ArgDict {
         // Quit if not enough arguments
         if args.len() < 1 {
             panic!("Not enough arguments given; requires at least an executable as first argument.");
         }
 
         // Prepare the resulting dict of arguments
-        let mut result = ArgDict::new(self.use_help);
+        let mut result = ArgDict::new(self.use_help, &args[0], self.messages.clone());
+
+        // Remember how the options take values, so the dict can render them again
+        result.value_limits = self.options.iter().map(|o| (o.uid.clone(), (o.max_n_values, o.repeatable || o.map, o.value_terminator.clone()))).collect();
+
+        // Splice in the contents of any response files first
+        let expanded: Vec<String>;
+        let args = if self.use_resp_files {
+            expanded = ArgParser::expand_resp_files(args, &mut result.errors);
+            &expanded
+        } else {
+            args
+        };
 
         // Now go through the arguments to parse them
+        let mut pos_values: Vec<String> = Vec::new();
+        let mut pos_trace: Vec<usize> = Vec::new();
+        let mut pos_indices: Vec<usize> = Vec::new();
+        let mut occurrences: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        let mut short_opt: std::option::Option<(&Option, String)> = None;
         let mut positional_i = 0;
-        let mut parse_options = true;
+        let mut extra_pos = false;
         let mut i: usize = 1;
         while i < args.len() {
             // Get the argument and its iterator
             let arg = &args[i];
             let sarg = OpString::new(arg);
-            if sarg.len() == 0 { continue; }
-
-            // First, split on option or not
-            if parse_options && sarg[0].eq("-") {
-                // It's an option
-                if sarg.len() == 1 {
-                    result.errors.push(String::from("Missing character after '-'."));
-                    i += 1;
-                    continue;
-                }
+            if sarg.len() == 0 {
+                // Skip empty arguments (unless they're the value of an option)
+                trace.push((arg.clone(), String::from("skipped")));
+                i += 1;
+                continue;
+            }
 
+            // First, split on option or not (a lone dash is a positional, conventionally meaning stdin, and so is a bare double dash if it isn't used as separator)
+            if result.double_dash.is_none() && self.is_opt_prefix(sarg[0]) && sarg.len() > 1 && (self.use_double_dash || !(sarg.len() == 2 && sarg[1].eq(sarg[0]))) {
                 // If it's the double dash case, then stop parsing double values
-                if self.use_double_dash && sarg.len() == 2 && sarg[1].eq("-") {
-                    parse_options = false;
+                if self.use_double_dash && sarg.len() == 2 && sarg[1].eq(sarg[0]) {
+                    trace.push((arg.clone(), String::from("separator")));
+                    result.double_dash = Some(i);
                     i += 1;
                     continue;
                 }
 
                 // Check if single dash or double dash
-                if !sarg[1].eq("-") || (!self.use_double_dash && sarg.len() == 2) {
+                if !sarg[1].eq(sarg[0]) {
                     // Single dash; shortoption
                     let mut found = false;
                     let mut error = false;
                     for o in self.options.iter() {
-                        if o.shortname.eq(sarg[1]) {
+                        if self.names_eq(&o.shortname, sarg[1]) || o.short_aliases.iter().any(|a| self.names_eq(a, sarg[1])) {
                             // It's a match!
 
+                            // Counted options may be repeated within the same argument (e.g., '-vvv')
+                            let mut n_repeats: usize = 1;
+                            if o.counted && sarg.len() > 2 && (2..sarg.len()).all(|j| sarg[j].eq(sarg[1])) {
+                                n_repeats = sarg.len() - 1;
+                            }
+
+                            // An inline value may be separated from the shortname with an equals sign (e.g., '-o=value')
+                            let value_start: usize = if sarg.len() > 2 && sarg[2].eq("=") { 3 } else { 2 };
+
                             // Make sure it's legal
-                            if sarg.len() > 2 {
+                            if sarg.len() > 2 && n_repeats == 1 {
                                 if o.max_n_values == 0 {
                                     // No values at all supported
-                                    result.errors.push(format!("Option '-{}' cannot accept values (is passed '{}').", o.shortname, &arg[sarg.translate_opstr(2)..]));
+                                    result.errors.push(ParseError::UnexpectedValue{ option: format!("-{}", o.shortname), index: i, value: String::from(&arg[sarg.translate_opstr(value_start)..]) });
                                     error = true;
                                     break;
-                                } else if o.max_n_values > 1 {
+                                } else if o.max_n_values > 1 && !o.map {
                                     // More values supported
-                                    result.errors.push(format!("Passing a value immediately after an option is only supported for options with at most 1 value ('-{}' has at most {}).", o.shortname, o.max_n_values));
+                                    result.errors.push(ParseError::InlineValueUnsupported{ option: format!("-{}", o.shortname), index: i, max: o.max_n_values });
                                     error = true;
                                     break;
                                 }
                             }
 
                             // Now make sure the option is defined
+                            let name = format!("-{}", o.shortname);
+                            trace.push((arg.clone(), format!("option:{}", name)));
+                            if o.negatable { result.flags.insert(o.uid.clone(), (o.longname.clone(), true)); }
+                            if o.counted { *result.counts.entry(o.uid.clone()).or_insert(0) += n_repeats; }
                             if !result.options.contains_key(&o.uid) {
                                 result.options.insert(o.uid.clone(), (o.shortname.clone(), o.longname.clone(), Vec::new()));
                             }
                             let values = &mut result.options.get_mut(&o.uid).unwrap().2;
+                            let n_before = values.len();
+                            let option_i = i;
                             
                             // Add the values as needed
-                            if sarg.len() > 2 {
+                            if sarg.len() > 2 && n_repeats == 1 {
                                 // We know that the number of arguments make sense, so add the rest as a value
-                                values.push(String::from(&arg[sarg.translate_opstr(2)..]));
+                                values.push(String::from(&arg[sarg.translate_opstr(value_start)..]));
+
+                            } else if o.rest && !extra_pos {
+                                // Take all remaining arguments verbatim
+                                values.extend_from_slice(&args[i + 1..]);
+                                trace.extend(args[i + 1..].iter().map(|a| (a.clone(), format!("value-of:{}", name))));
+                                i = args.len() - 1;
 
-                            } else if o.max_n_values > 0 {
+                            } else if o.max_n_values > 0 && !extra_pos {
                                 // Parse the rest of the arguments as values
-                                let mut new_values = ArgParser::parse_values(args, &mut i, o.max_n_values - values.len(), &mut parse_options, self.use_double_dash);
+                                let mut new_values = self.parse_values(args, &mut i, if o.map || o.max_n_values == 1 { 1 } else if o.repeatable { o.max_n_values } else { o.max_n_values - values.len() }, o.value_terminator.as_deref(), o.allow_dash_values, &mut result.double_dash, &name, trace);
                                 values.append(&mut new_values);
 
                             }
 
+                            // Remember how many values this occurrence got
+                            occurrences.entry(o.uid.clone()).or_default().push((option_i, values.len() - n_before));
+
+                            // If told to, remember it if it's still short of values so following positionals can fill it up
+                            if self.greedy_values && !extra_pos && values.len() < o.min_n_values { short_opt = Some((o, name)); }
+
                             // We're done
                             found = true;
                             break;
                         }
                     }
 
-                    // If not found, throw an error
+                    // If not found, throw an error (or stop, if parsing partially)
                     if !found {
-                        if !error { result.errors.push(format!("Unknown option '{}'{}", arg, if self.use_help { "; use '--help' to see an overview of accepted options." } else { "" })); }
+                        if !error && remainder.is_some() {
+                            *remainder = Some(args[i..].to_vec());
+                            break;
+                        }
+                        if !error && self.collect_unknown {
+                            // Collect it as given, together with any values given separately from it
+                            result.unknown.push(arg.clone());
+                            trace.push((arg.clone(), String::from("unknown")));
+                            if sarg.len() == 2 {
+                                let values = self.parse_unknown_values(args, &mut i, arg, trace);
+                                result.unknown.extend(values);
+                            }
+                            i += 1;
+                            continue;
+                        }
+                        if !error { result.errors.push(ParseError::UnknownOption{ option: arg.clone(), index: i, help_hint: self.use_help, suggestion: self.suggest_option(arg) }); }
+                        trace.push((arg.clone(), String::from(if error { "invalid" } else { "unknown" })));
                         i += 1;
                         continue;
                     }
@@ -990,113 +6318,421 @@ impl ArgParser {
                     let mut found = false;
                     let mut error = false;
                     let larg = &arg[sarg.translate_opstr(2)..];
+                    if larg.starts_with('=') {
+                        // There's a value but no name, so don't even try to match it
+                        result.errors.push(ParseError::MissingOptionName{ index: i, value: String::from(&larg[1..]) });
+                        error = true;
+                    }
                     for o in self.options.iter() {
-                        if o.longname.eq(&larg[..o.longname.len()]) {
+                        if error { break; }
+
+                        // Check if it's the negated form of a flag
+                        if o.negatable && larg.len() == 3 + o.longname.len() && larg.is_char_boundary(3) && self.names_eq("no-", &larg[..3]) && self.names_eq(&o.longname, &larg[3..]) {
+                            // Set the flag to false, overriding any earlier occurrence
+                            result.flags.insert(o.uid.clone(), (o.longname.clone(), false));
+                            result.options.remove(&o.uid);
+                            trace.push((arg.clone(), format!("option:--no-{}", o.longname)));
+
+                            // We're done
+                            found = true;
+                            break;
+                        }
+
+                        // Find which of the option's names (if any) is given, either by itself or followed by an equals sign (note that name_len is in bytes, so it only indexes larg, not sarg)
+                        let mut name_len: std::option::Option<usize> = None;
+                        for name in std::iter::once(&o.longname).chain(o.aliases.iter()) {
+                            if larg.len() >= name.len() && larg.is_char_boundary(name.len()) && self.names_eq(name, &larg[..name.len()]) && (larg.len() == name.len() || larg[name.len()..].starts_with('=')) {
+                                name_len = Some(name.len());
+                                break;
+                            }
+                        }
+
+                        if let Some(name_len) = name_len {
                             // It's a match!
 
                             // Make sure its legal
-                            if larg.len() > o.longname.len() {
-                                if !sarg[2 + o.longname.len()].eq("=") {
-                                    // Not yet the end; continue instead
-                                    continue;
-                                } else if o.max_n_values == 0 {
+                            if larg.len() > name_len {
+                                if o.max_n_values == 0 {
                                     // No values at all supported
-                                    result.errors.push(format!("Option '--{}' cannot accept values (is passed '{}').", o.longname, &arg[2 + o.longname.len() + 1..]));
+                                    result.errors.push(ParseError::UnexpectedValue{ option: format!("--{}", o.longname), index: i, value: String::from(&larg[name_len + 1..]) });
                                     error = true;
                                     break;
-                                } else if o.max_n_values > 1 {
+                                } else if o.max_n_values > 1 && !o.map {
                                     // More values supported
-                                    result.errors.push(format!("Passing a value immediately after an option is only supported for options with at most 1 value ('--{}' has at most {}).", o.longname, o.max_n_values));
+                                    result.errors.push(ParseError::InlineValueUnsupported{ option: format!("--{}", o.longname), index: i, max: o.max_n_values });
                                     error = true;
                                     break;
                                 }
                             }
 
                             // Otherwise, make sure the option is defined
+                            let name = format!("--{}", o.longname);
+                            trace.push((arg.clone(), format!("option:{}", name)));
+                            if o.negatable { result.flags.insert(o.uid.clone(), (o.longname.clone(), true)); }
+                            if o.counted { *result.counts.entry(o.uid.clone()).or_insert(0) += 1; }
                             if !result.options.contains_key(&o.uid) {
                                 result.options.insert(o.uid.clone(), (o.shortname.clone(), o.longname.clone(), Vec::new()));
                             }
                             let values = &mut result.options.get_mut(&o.uid).unwrap().2;
+                            let n_before = values.len();
+                            let option_i = i;
 
                             // Add the values as needed
-                            if larg.len() > o.longname.len() {
+                            if larg.len() > name_len {
                                 // We know that the equal sign and number of arguments make sense, so add the rest as a value
-                                values.push(String::from(&arg[2 + o.longname.len() + 1..]));
+                                values.push(String::from(&larg[name_len + 1..]));
 
-                            } else if o.max_n_values > 0 {
+                            } else if o.rest && !extra_pos {
+                                // Take all remaining arguments verbatim
+                                values.extend_from_slice(&args[i + 1..]);
+                                trace.extend(args[i + 1..].iter().map(|a| (a.clone(), format!("value-of:{}", name))));
+                                i = args.len() - 1;
+
+                            } else if o.max_n_values > 0 && !extra_pos {
                                 // Parse the rest of the arguments as values
-                                let mut new_values = ArgParser::parse_values(args, &mut i, o.max_n_values - values.len(), &mut parse_options, self.use_double_dash);
+                                let mut new_values = self.parse_values(args, &mut i, if o.map || o.max_n_values == 1 { 1 } else if o.repeatable { o.max_n_values } else { o.max_n_values - values.len() }, o.value_terminator.as_deref(), o.allow_dash_values, &mut result.double_dash, &name, trace);
                                 values.append(&mut new_values);
 
                             }
 
+                            // Remember how many values this occurrence got
+                            occurrences.entry(o.uid.clone()).or_default().push((option_i, values.len() - n_before));
+
+                            // If told to, remember it if it's still short of values so following positionals can fill it up
+                            if self.greedy_values && !extra_pos && values.len() < o.min_n_values { short_opt = Some((o, name)); }
+
                             // We're done
                             found = true;
                             break;
                         }
                     }
 
-                    // If not found, throw an error
+                    // If not found, throw an error (or stop, if parsing partially)
                     if !found {
-                        if !error { result.errors.push(format!("Unknown option '{}'{}", arg, if self.use_help { "; use '--help' to see an overview of accepted options." } else { "" })); }
+                        if !error && remainder.is_some() {
+                            *remainder = Some(args[i..].to_vec());
+                            break;
+                        }
+                        if !error && self.collect_unknown {
+                            // Collect it as given, together with any values given separately from it
+                            result.unknown.push(arg.clone());
+                            trace.push((arg.clone(), String::from("unknown")));
+                            if !larg.contains('=') {
+                                let values = self.parse_unknown_values(args, &mut i, arg, trace);
+                                result.unknown.extend(values);
+                            }
+                            i += 1;
+                            continue;
+                        }
+                        if !error { result.errors.push(ParseError::UnknownOption{ option: arg.clone(), index: i, help_hint: self.use_help, suggestion: self.suggest_option(arg) }); }
+                        trace.push((arg.clone(), String::from(if error { "invalid" } else { "unknown" })));
                         i += 1;
                         continue;
                     }
                 }
 
             } else {
-                // It's a positional; check if we have any registered
-                if positional_i >= self.positionals.len() {
-                    result.warnings.push(format!("Skipping positional '{}' (index {})...", sarg, positional_i));
-                    i += 1;
-                    positional_i += 1;
-                    continue;
+                // It's a positional; if an option is still short of values, give it to that instead
+                if let Some((o, name)) = short_opt.take() {
+                    let values = &mut result.options.get_mut(&o.uid).unwrap().2;
+                    if values.len() < o.min_n_values {
+                        values.push(arg.clone());
+                        if let Some((_, n)) = occurrences.get_mut(&o.uid).and_then(|counts| counts.last_mut()) { *n += 1; }
+                        trace.push((arg.clone(), format!("value-of:{}", name)));
+                        if values.len() < o.min_n_values { short_opt = Some((o, name)); }
+                        i += 1;
+                        continue;
+                    }
+                }
+
+                // Otherwise, if it's the first, check if it's a subcommand
+                if positional_i == 0 && self.subcommands.len() > 0 {
+                    // Try to find the subcommand
+                    match self.subcommands.iter().find(|(n, _)| n.eq(arg)) {
+                        Some((name, parser)) => {
+                            // Let the subcommand parse the rest of the arguments
+                            let mut sub_args: Vec<String> = vec!(format!("{} {}", args[0], name));
+                            sub_args.extend_from_slice(&args[i + 1..]);
+                            trace.push((arg.clone(), format!("subcommand:{}", name)));
+                            let sub_dict = parser.parse_traced(&sub_args, trace, &mut None);
+
+                            // Propagate its warnings and errors, then store it
+                            result.warnings.extend_from_slice(&sub_dict.warnings);
+                            result.errors.extend(sub_dict.errors.iter().map(|e| e.clone().offset_index(i)));
+                            result.subcommand = Some((name.clone(), Box::new(sub_dict)));
+                        },
+                        None => {
+                            result.errors.push(ParseError::UnknownSubcommand{ subcommand: arg.clone(), expected: self.subcommands.iter().map(|(n, _)| n.clone()).collect() });
+                            trace.push((arg.clone(), String::from("unknown")));
+                            trace.extend(args[i + 1..].iter().map(|a| (a.clone(), String::from("skipped"))));
+                        }
+                    }
+
+                    // The rest of the arguments is not ours anymore
+                    break;
+                }
+
+                // If parsing partially, stop if there's no positional left for it
+                if remainder.is_some() && positional_i >= self.positionals.len() && !self.positionals.iter().any(|p| p.multi) {
+                    *remainder = Some(args[i..].to_vec());
+                    break;
                 }
 
-                // We have, so add it
-                result.positionals.insert(self.positionals[positional_i].uid.clone(), (self.positionals[positional_i].index, arg.clone()));
+                // Otherwise, store it for when we know how many there are
+                pos_values.push(arg.clone());
+                pos_trace.push(trace.len());
+                pos_indices.push(i);
+                trace.push((arg.clone(), String::new()));
                 positional_i += 1;
 
+                // Once there are more positionals than we can take, options no longer take separate values (so the rest is skipped deterministically)
+                if positional_i > self.positionals.len() && !self.positionals.iter().any(|p| p.multi) { extra_pos = true; }
+
+                // If told to, pass the rest of the arguments through as-is
+                if self.stop_at_first_pos {
+                    result.trailing.extend_from_slice(&args[i + 1..]);
+                    trace.extend(args[i + 1..].iter().map(|a| (a.clone(), String::from("trailing"))));
+                    break;
+                }
+
             }
 
             // Done, increment i
             i += 1;
         }
 
+        // Assign the positional values now we know how many there are
+        let assigned = self.assign_positionals(&pos_values, &mut result);
+        for (trace_i, uid) in pos_trace.into_iter().zip(assigned.iter()) {
+            trace[trace_i].1 = match uid {
+                Some(uid) => format!("positional:{}", uid),
+                None      => String::from("skipped"),
+            };
+        }
+
+        // Verify the values of the positionals, if restricted
+        for ((value, index), uid) in pos_values.iter().zip(pos_indices.into_iter()).zip(assigned.into_iter()) {
+            // Get the positional it was assigned to, if any
+            let pos = match uid.and_then(|uid| self.positionals.iter().find(|p| p.uid.eq(&uid))) { Some(pos) => pos, None => { continue; } };
+
+            // Check it
+            if pos.choices.len() > 0 && !pos.choices.contains(value) {
+                result.errors.push(ParseError::InvalidChoice{ option: format!("<{}>", pos.name), index: Some(index), value: value.clone(), choices: pos.choices.clone() });
+            } else if let Some(Err(reason)) = pos.validator.map(|v| v(value)) {
+                result.errors.push(ParseError::InvalidValue{ option: format!("<{}>", pos.name), index: Some(index), value: value.clone(), reason: reason });
+            }
+        }
+
+        // Remember for each value given on the command line which occurrence of its option it was given to
+        let mut value_indices: HashMap<String, Vec<usize>> = occurrences.iter().map(|(uid, counts)| (uid.clone(), counts.iter().flat_map(|(index, n)| std::iter::repeat(*index).take(*n)).collect())).collect();
+
+        // Fill in any options not given with their environment variables, if any
+        for opt in self.options.iter() {
+            // Skip the option if it has no variable or the user already gave it
+            if opt.env_var.len() == 0 || result.options.contains_key(&opt.uid) { continue; }
+
+            // Try to read the variable
+            if let Ok(value) = std::env::var(&opt.env_var) {
+                // Flags are not given if the variable says so
+                if opt.max_n_values == 0 && ["", "0", "false", "no"].contains(&value.trim().to_lowercase().as_str()) { continue; }
+                result.options.insert(opt.uid.clone(), (opt.shortname.clone(), opt.longname.clone(), ArgParser::split_fallback_value(opt, &value)));
+                result.sources.insert(opt.uid.clone(), Source::Env);
+            }
+        }
+
+        // Fill in any options still not given with their defaults, if any
+        for opt in self.options.iter() {
+            // Skip the option if it has no default or it's given already
+            let value = match &opt.default_value { Some(value) => value, None => { continue; } };
+            if result.options.contains_key(&opt.uid) { continue; }
+
+            // Store it
+            result.options.insert(opt.uid.clone(), (opt.shortname.clone(), opt.longname.clone(), ArgParser::split_fallback_value(opt, value)));
+            result.sources.insert(opt.uid.clone(), Source::Default);
+        }
+
+        // Split the values of any options with a delimiter
+        for opt in self.options.iter() {
+            // Skip the option if it has no delimiter or the user never gave it
+            let delimiter = match opt.value_delimiter { Some(delimiter) => delimiter, None => { continue; } };
+            let values = match result.options.get_mut(&opt.uid) { Some(entry) => &mut entry.2, None => { continue; } };
+
+            // Split all values (keeping track of where they were given)
+            let indices = value_indices.get(&opt.uid);
+            let mut new_values: Vec<String> = Vec::with_capacity(values.len());
+            let mut new_indices: Vec<usize> = Vec::with_capacity(values.len());
+            for (j, value) in values.iter().enumerate() {
+                for part in value.split(delimiter) {
+                    if part.len() > 0 || opt.keep_empty_values {
+                        new_values.push(String::from(part));
+                        if let Some(indices) = indices { new_indices.push(indices[j]); }
+                    }
+                }
+            }
+            *values = new_values;
+            if indices.is_some() { value_indices.insert(opt.uid.clone(), new_indices); }
+        }
+
+        // Replace the paths given to options that read from a file with the contents of that file
+        for opt in self.options.iter() {
+            // Skip the option if it doesn't read from a file or the user never gave it
+            let trim = match opt.from_file { Some(trim) => trim, None => { continue; } };
+            let values = match result.options.get_mut(&opt.uid) { Some(entry) => &mut entry.2, None => { continue; } };
+
+            // Read the files
+            for (j, value) in values.iter_mut().enumerate() {
+                match std::fs::read_to_string(&value) {
+                    Ok(contents) => { *value = if trim { String::from(contents.trim()) } else { contents }; },
+                    Err(reason)  => { result.errors.push(ParseError::UnreadableFile{ option: format!("--{}", opt.longname), index: value_indices.get(&opt.uid).and_then(|indices| indices.get(j)).copied(), reason: reason.to_string() }); },
+                }
+            }
+        }
+
         // Check if each option has enough values
         for opt in self.options.iter() {
             // Skip the option if the user never gave it
             if !result.options.contains_key(&opt.uid) { continue; }
 
-            // Verify the number of values
+            // Verify the number of values (of each occurrence separately if the option is repeatable, or in total otherwise)
             let values = &result.options.get(&opt.uid).unwrap().2;
-            if values.len() < opt.min_n_values {
-                result.errors.push(format!("Not enough values for '--{}': expected at least {}, got {}.", opt.longname, opt.min_n_values, values.len()));
-            } else if values.len() > opt.max_n_values {
-                result.errors.push(format!("Too many values for '--{}': expected at most {}, got {}.", opt.longname, opt.max_n_values, values.len()));
+            let value_index = |j: usize| value_indices.get(&opt.uid).and_then(|indices| indices.get(j)).copied();
+            let counts: Vec<(std::option::Option<usize>, usize)> = match occurrences.get(&opt.uid) {
+                Some(counts) if opt.repeatable => counts.iter().map(|(index, n)| (Some(*index), *n)).collect(),
+                Some(counts)                   => vec!((counts.last().map(|(index, _)| *index), values.len())),
+                None                           => vec!((None, values.len())),
+            };
+            if opt.max_n_values == 1 && !opt.map && !opt.repeatable && occurrences.get(&opt.uid).map(|counts| counts.len()).unwrap_or(0) > 1 {
+                // Single-value options that are repeated get a clearer error than just too many values
+                result.errors.push(ParseError::DuplicateOption{ option: format!("--{}", opt.longname), index: occurrences.get(&opt.uid).map(|counts| counts[1].0) });
+            } else if let Some((index, got)) = counts.iter().find(|(_, n)| *n < opt.min_n_values) {
+                result.errors.push(ParseError::MissingValues{ option: format!("--{}", opt.longname), index: *index, expected: opt.min_n_values, got: *got });
+            } else if let Some((index, got)) = counts.iter().find(|(_, n)| *n > opt.max_n_values) {
+                result.errors.push(ParseError::TooManyValues{ option: format!("--{}", opt.longname), index: *index, expected: opt.max_n_values, got: *got });
+            }
+
+            // Verify the values are pairs, if a map
+            if opt.map {
+                for (j, value) in values.iter().enumerate() {
+                    if !value.contains('=') {
+                        result.errors.push(ParseError::InvalidKeyValue{ option: format!("--{}", opt.longname), index: value_index(j), value: value.clone() });
+                    }
+                }
+            }
+
+            // Verify the values themselves, if restricted
+            if opt.choices.len() > 0 {
+                for (j, value) in values.iter().enumerate() {
+                    if !opt.choices.contains(value) {
+                        result.errors.push(ParseError::InvalidChoice{ option: format!("--{}", opt.longname), index: value_index(j), value: value.clone(), choices: opt.choices.clone() });
+                    }
+                }
+            }
+            if let Some((min, max)) = opt.int_range {
+                for (j, value) in values.iter().enumerate() {
+                    match value.parse::<i64>() {
+                        Ok(n) if n < min || n > max => { result.errors.push(ParseError::OutOfRange{ option: format!("--{}", opt.longname), index: value_index(j), value: n, min, max }); },
+                        Ok(_)                       => {},
+                        Err(err)                    => { result.errors.push(ParseError::InvalidValue{ option: format!("--{}", opt.longname), index: value_index(j), value: value.clone(), reason: err.to_string() }); },
+                    }
+                }
+            }
+        }
+
+        // Check if all required options are given (including those that are required because they need values)
+        for opt in self.options.iter() {
+            if (opt.required || (opt.require_if_min && opt.min_n_values > 0)) && !result.options.contains_key(&opt.uid) {
+                result.errors.push(ParseError::MissingRequired{ option: format!("--{}", opt.longname) });
+            }
+        }
+
+        // Check if at most one option of each mutually exclusive group is given (where defaults don't count as given, since the user didn't choose them)
+        for group in self.mutex_groups.iter() {
+            let given: Vec<String> = group.iter().filter(|uid| result.opt_source(uid).map(|s| s != Source::Default).unwrap_or(false)).map(|uid| format!("--{}", self.get_longname(uid))).collect();
+            if given.len() > 1 {
+                result.errors.push(ParseError::MutuallyExclusive{ options: given });
             }
         }
 
+        // Check if exactly one option of each required group is given (where a default only counts if nothing else is given)
+        for group in self.required_groups.iter() {
+            let given: Vec<String> = group.iter().filter(|uid| result.opt_source(uid).map(|s| s != Source::Default).unwrap_or(false)).map(|uid| format!("--{}", self.get_longname(uid))).collect();
+            if given.len() == 0 && !group.iter().any(|uid| result.options.contains_key(uid)) {
+                result.errors.push(ParseError::MissingOneOf{ options: group.iter().map(|uid| format!("--{}", self.get_longname(uid))).collect() });
+            } else if given.len() > 1 {
+                result.errors.push(ParseError::MutuallyExclusive{ options: given });
+            }
+        }
+
+        // Check if the requirements of each given option are met (options that are only defaulted require nothing, but a default does satisfy a requirement)
+        for (uid, requires) in self.requirements.iter() {
+            if result.opt_source(uid).map(|s| s != Source::Default).unwrap_or(false) && !result.options.contains_key(requires) {
+                result.errors.push(ParseError::MissingDependency{ option: format!("--{}", self.get_longname(uid)), requires: format!("--{}", self.get_longname(requires)) });
+            }
+        }
+
+        // Run the custom validator, if any
+        if let Some(validator) = &self.post_validator {
+            let messages = (validator.0)(&result);
+            result.errors.extend(messages.into_iter().map(|message| ParseError::Custom{ message }));
+        }
+
         // Clear the values if help is given (leaving help in that case) or, if not, there are errors
         if self.use_help && result.options.contains_key(HELP_UID) {
-            // Clear the errors and the warnings
-            result.warnings.clear();
-            result.errors.clear();
+            // Clear the errors and the warnings, if told to do so
+            if self.help_supp_errors {
+                result.warnings.clear();
+                result.errors.clear();
+                result.n_skipped_pos = 0;
+            }
             // Clear the positionals & options, except help
             result.positionals.clear();
+            result.multi_positionals.clear();
             result.options.retain(|key, _| key.eq(HELP_UID) );
-            // Show the help string
-            print!("{}", self.get_help(&args[0], 20, 80));
+            result.sources.clear();
+            result.flags.clear();
+            result.counts.clear();
+            result.subcommand = None;
+            result.trailing.clear();
+            result.unknown.clear();
+            // Show the help string, if told to do so
+            let _ = self.write_auto_help(&mut io::stdout(), &args[0], std::env::var("COLUMNS").ok().as_deref());
         } else if result.errors.len() > 0 {
             // Clear everything that isn't a warning or an error
             result.positionals.clear();
+            result.multi_positionals.clear();
             result.options.clear();
+            result.sources.clear();
+            result.flags.clear();
+            result.counts.clear();
+            result.subcommand = None;
+            result.trailing.clear();
+            result.unknown.clear();
         }
 
         // Done! Return the result
         return result;
     }
 
+    /// Tries to parse the internally defined positionals and arguments according to the given list of arguments, returning any errors as a Result.
+    /// 
+    /// Works exactly like `parse()`, except that the errors are not stored in the dict but returned instead.
+    /// 
+    /// ** Arguments **
+    ///  * `args`: The list of arguments, as a vector of str's.
+    /// 
+    /// ** Returns **
+    /// The ArgDict with the results (and any warnings) if no errors occurred, or else the list of errors (in the order they occurred).
+    pub fn try_parse(&self, args: &Vec<String>) -> Result<ArgDict, ArgErrors> {
+        // Simply parse as usual
+        let mut result = self.parse(args);
+
+        // Split on whether errors occurred or not
+        if result.errors.len() > 0 {
+            return Err(ArgErrors(std::mem::take(&mut result.errors)));
+        }
+        return Ok(result);
+    }
+
 }
 
 
@@ -1104,33 +6740,230 @@ impl ArgParser {
 
 
 /***** ARGDICT CLASS *****/
+/// Defines where the values of an option in the ArgDict come from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Source {
+    /// The option was given on the command line.
+    CommandLine,
+    /// The option was not given, so its default was used (see `ArgParser::set_default()`).
+    Default,
+    /// The option was not given, so its environment variable was used (see `ArgParser::add_opt_env()`).
+    Env,
+}
+
 /// Defines a dictionary that is returned by the ArgParser, and can be used to lookup parsed positionals and options.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArgDict {
     /// Stores whether or not help is used.
-    use_help    : bool,
+    use_help          : bool,
+    /// Stores the name of the executable, i.e., the first argument given.
+    exec_name         : String,
+    /// Stores the templates used to write the errors.
+    messages          : Messages,
+
+    /// Stores the parsed positionals. Each positional is mapped to its uid, and contains its index and string value.
+    positionals       : PositionalHashMap,
+    /// Stores the parsed variadic positionals. Each positional is mapped to its uid, and contains its index and all its string values.
+    multi_positionals : MultiPositionalHashMap,
+    /// Stores the parsed options. Each option is mapped to its uid.
+    options           : OptionHashMap,
+    /// Stores the state of any negatable flags given. Each flag is mapped to its uid, and contains its longname and state.
+    flags             : FlagHashMap,
+    /// Stores how often each counted option was given. Each option is mapped to its uid.
+    counts            : CountHashMap,
+    /// Stores where the values of the options that were not given on the command line come from. Each option is mapped to its uid.
+    sources           : SourceHashMap,
+    /// Stores how the options of the parser take values, so they can be rendered again. Each option is mapped to its uid.
+    value_limits      : LimitHashMap,
+    /// Stores the subcommand given (if any), together with the dict that resulted from parsing its arguments.
+    subcommand        : std::option::Option<(String, Box<ArgDict>)>,
+    /// Stores the arguments following the first positional, if the parser stops there.
+    trailing          : Vec<String>,
+    /// Stores the unknown options given, if the parser collects them.
+    unknown           : Vec<String>,
+    /// Stores the index of the double dash that stopped the parsing of options, if any.
+    double_dash       : std::option::Option<usize>,
+    /// Stores the number of positional values that were skipped because there were no positionals left to assign them to.
+    n_skipped_pos     : usize,
+
+    /// Stores any warnings encountered during parsing.
+    warnings          : Vec<ParseWarning>,
+    /// Stores any errors encountered during parsing. If this is non-empty, then there won't be any positionals or options either.
+    errors            : Vec<ParseError>,
+}
+
+/// Defines the ArgDict's methods
+impl ArgDict {
+    /// Private constructor for the ArgDict
+    fn new(use_help: bool, exec_name: &str, messages: Messages) -> ArgDict {
+        ArgDict {
+            use_help          : use_help,
+            exec_name         : String::from(exec_name),
+            messages          : messages,
+            positionals       : PositionalHashMap::new(),
+            multi_positionals : MultiPositionalHashMap::new(),
+            options           : OptionHashMap::new(),
+            flags             : FlagHashMap::new(),
+            counts            : CountHashMap::new(),
+            sources           : SourceHashMap::new(),
+            value_limits      : LimitHashMap::new(),
+            subcommand        : None,
+            trailing          : Vec::new(),
+            unknown           : Vec::new(),
+            double_dash       : None,
+            n_skipped_pos     : 0,
+            warnings          : Vec::new(),
+            errors            : Vec::new()
+        }
+    }
+
+
+
+    /// Merges the given ArgDict into this one, e.g., to layer the arguments given on the command line over some defaults.
+    /// 
+    /// Any positional, option, flag, count or subcommand given in the other dict replaces the one in this dict. In particular, the values of an option are replaced as a whole instead of appended to. The warnings and errors of the other dict are appended to the ones in this dict, and the executable name is taken from the other dict.
+    /// 
+    /// **Arguments**
+    ///  * `other`: The ArgDict to merge into this one. Takes precedence over this one.
+    pub fn merge(&mut self, other: ArgDict) {
+        // Override everything given in the other one
+        self.use_help  = self.use_help || other.use_help;
+        self.exec_name = other.exec_name;
+        self.positionals.extend(other.positionals);
+        self.multi_positionals.extend(other.multi_positionals);
+        for uid in other.options.keys() { self.sources.remove(uid); }
+        self.sources.extend(other.sources);
+        self.value_limits.extend(other.value_limits);
+        self.options.extend(other.options);
+        self.flags.extend(other.flags);
+        self.counts.extend(other.counts);
+        if other.subcommand.is_some() { self.subcommand = other.subcommand; }
+        if other.trailing.len() > 0 { self.trailing = other.trailing; }
+        self.unknown.extend(other.unknown);
+        if other.double_dash.is_some() { self.double_dash = other.double_dash; }
+
+        // Append the warnings and errors
+        self.warnings.extend(other.warnings);
+        self.errors.extend(other.errors);
+        self.n_skipped_pos += other.n_skipped_pos;
+    }
+
+    /// Renders the parsed positionals and options back into a normalized command line (without the executable name), e.g., for audit logs.
+    /// 
+    /// The result is not identical to the original arguments, but parses to the same dict. Positionals come first in the order of their indices, followed by the options by their longnames (one occurrence per value), and then the subcommand with its own arguments. If the parser stopped at the first positional, the options come before it instead, followed by the trailing arguments. Arguments that the shell would split or interpret are quoted.
+    /// 
+    /// If anything follows the options, an option that cannot take more values is moved to the end so it isn't given what follows. If there is none, the values of the last option are ended with its value terminator (see `ArgParser::set_value_terminator()`); without one, such a command line does not parse back the same.
+    /// 
+    /// Values that start with a dash are given immediately after the option (e.g., '--output=-x'), so they aren't mistaken for options. Note that such values only parse back for options that accept at most one value (or map options), since others cannot be given values that way. Likewise, positionals that start with a dash are not supported, and neither are options that take all remaining arguments (see `add_opt_rest()`).
+    /// 
+    /// **Returns**  
+    /// The normalized command line as a single string.
+    pub fn reconstruct(&self) -> String {
+        // Collect the options, sorted on their longnames so the result is deterministic
+        let mut options: Vec<(&String, &(String, String, Vec<String>))> = self.options.iter().collect();
+        options.sort_by(|(_, (_, l1, _)), (_, (_, l2, _))| l1.cmp(l2));
+        let mut opt_groups: Vec<(Vec<String>, bool, std::option::Option<&String>)> = Vec::new();
+        for (uid, (_, longname, values)) in options {
+            let name = format!("--{}", longname);
+            let mut words: Vec<String> = Vec::new();
+            if values.len() == 0 {
+                // Flags are repeated as often as they are counted
+                for _ in 0..self.counts.get(uid).copied().unwrap_or(1).max(1) { words.push(name.clone()); }
+            } else {
+                // Values accumulate over occurrences, so give each value its own (which also works for maps), attaching the ones that look like options
+                for value in values {
+                    if value.starts_with('-') {
+                        words.push(format!("{}={}", name, ArgDict::quote(value)));
+                    } else {
+                        words.push(name.clone());
+                        words.push(ArgDict::quote(value));
+                    }
+                }
+            }
+
+            // Note whether the last occurrence is full (i.e., it cannot take what follows it), and what would end it otherwise
+            let (full, terminator) = match self.value_limits.get(uid) {
+                Some((max, per_occurrence, terminator)) => (if *per_occurrence { *max <= 1 } else { values.len() >= *max }, terminator.as_ref()),
+                None                                    => (false, None),
+            };
+            opt_groups.push((words, full, terminator));
+        }
+
+        // Add the negated flags (which are not in the options)
+        let mut negated: Vec<&String> = self.flags.values().filter(|(_, state)| !*state).map(|(longname, _)| longname).collect();
+        negated.sort();
+        opt_groups.extend(negated.into_iter().map(|longname| (vec!(format!("--no-{}", longname)), true, None)));
+
+        // If anything follows the options, make sure the last one doesn't take it
+        if (self.trailing.len() > 0 || self.subcommand.is_some()) && opt_groups.len() > 0 && !opt_groups[opt_groups.len() - 1].1 {
+            if let Some(full_i) = opt_groups.iter().rposition(|(_, full, _)| *full) {
+                // Move an option that is full to the end
+                let group = opt_groups.remove(full_i);
+                opt_groups.push(group);
+            } else if let Some(terminator) = opt_groups[opt_groups.len() - 1].2 {
+                // End it with its terminator
+                let terminator = ArgDict::quote(terminator);
+                opt_groups.last_mut().unwrap().0.push(terminator);
+            }
+        }
+        let opt_words: Vec<String> = opt_groups.into_iter().flat_map(|(words, _, _)| words).collect();
+
+        // Collect the positionals in the order of their indices (variadic ones also store their first value as a normal positional)
+        let mut positionals: Vec<(usize, Vec<&String>)> = self.positionals.iter().filter(|(uid, _)| !self.multi_positionals.contains_key(*uid)).map(|(_, (index, value))| (*index, vec!(value))).collect();
+        positionals.extend(self.multi_positionals.values().map(|(index, values)| (*index, values.iter().collect())));
+        positionals.sort_by_key(|(index, _)| *index);
+        let pos_words: Vec<String> = positionals.into_iter().flat_map(|(_, values)| values.into_iter().map(|v| ArgDict::quote(v))).collect();
+
+        // Put them in the right order
+        let mut words: Vec<String> = Vec::new();
+        if self.trailing.len() > 0 {
+            // Anything after the first positional would be trailing, so the options go first
+            words.extend(opt_words);
+            words.extend(pos_words);
+            words.extend(self.trailing.iter().map(|t| ArgDict::quote(t)));
+        } else {
+            words.extend(pos_words);
+            words.extend(opt_words);
+        }
+
+        // Finally, add the subcommand with its own arguments
+        if let Some((name, dict)) = &self.subcommand {
+            words.push(ArgDict::quote(name));
+            let sub = dict.reconstruct();
+            if sub.len() > 0 { words.push(sub); }
+        }
+
+        // Done
+        return words.join(" ");
+    }
+
+    /// Quotes the given argument for use in a command line, if the shell would split or interpret it otherwise.
+    /// 
+    /// **Arguments**
+    ///  * `arg`: The argument to quote.
+    /// 
+    /// **Returns**  
+    /// The argument as-is if it's safe, or surrounded by single quotes otherwise.
+    fn quote(arg: &str) -> String {
+        if arg.len() > 0 && arg.chars().all(|c| c.is_alphanumeric() || "-_=+./:,@%".contains(c)) {
+            return String::from(arg);
+        }
+        return format!("'{}'", arg.replace('\'', "'\\''"));
+    }
 
-    /// Stores the parsed positionals. Each positional is mapped to its uid, and contains its index and string value.
-    positionals : PositionalHashMap,
-    /// Stores the parsed options. Each option is mapped to its uid.
-    options     : OptionHashMap,
 
-    /// Stores any warnings encountered during parsing.
-    warnings    : Vec<String>,
-    /// Stores any errors encountered during parsing. If this is non-empty, then there won't be any positionals or options either.
-    errors      : Vec<String>,
-}
 
-/// Defines the ArgDict's methods
-impl ArgDict {
-    /// Private constructor for the ArgDict
-    fn new(use_help: bool) -> ArgDict {
-        ArgDict {
-            use_help    : use_help,
-            positionals : PositionalHashMap::new(),
-            options     : OptionHashMap::new(),
-            warnings    : Vec::new(),
-            errors      : Vec::new()
-        }
+    /// Returns the name of the executable, i.e., the first argument given to `parse()`.
+    /// 
+    /// For the dict of a subcommand, this is the executable followed by the subcommand's name.
+    /// 
+    /// **Returns**  
+    /// The name of the executable, which can be passed to `get_usage()` or `get_help()`.
+    #[inline]
+    pub fn exec_name(&self) -> &str {
+        &self.exec_name
     }
 
 
@@ -1147,18 +6980,34 @@ impl ArgDict {
     /// Returns the internal errors as a vector.
     /// 
     /// **Returns**  
-    /// The errors as a Vec<String>. If there are no errors, it is empty.
+    /// The errors as a `Vec<ParseError>`. If there are no errors, it is empty.
     #[inline]
-    pub fn get_errors(&self) -> &Vec<String> {
+    pub fn get_errors(&self) -> &Vec<ParseError> {
         return &self.errors;
     }
 
+    /// If errors occurred, writes them one-by-one to the given writer, each on its own line, using the messages set with `set_messages()`.  
+    /// If there are no errors, does nothing.
+    /// 
+    /// **Arguments**
+    ///  * `w`: The writer to write the errors to.
+    /// 
+    /// **Returns**  
+    /// Nothing if the errors were written successfully, or the error that occurred while writing otherwise.
+    pub fn write_errors<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        // Simply write them all on the next line
+        for e in self.errors.iter() {
+            writeln!(w, "{}", self.messages.format(e))?;
+        }
+        return Ok(());
+    }
+
     /// If errors occurred, prints them one-by-one to stderr.  
     /// If there are no errors, does nothing.
     pub fn print_errors(&self) {
         // Simply print them all on the next line
         for e in self.errors.iter() {
-            eprint!("{}\n", e);
+            eprint!("{}\n", self.messages.format(e));
         }
     }
 
@@ -1176,18 +7025,34 @@ impl ArgDict {
     /// Returns the internal warnings as a vector.
     /// 
     /// **Returns**  
-    /// The warnings as a Vec<String>. If there are no warnings, it is empty.
+    /// The warnings as a `Vec<ParseWarning>`. If there are no warnings, it is empty.
     #[inline]
-    pub fn get_warnings(&self) -> &Vec<String> {
+    pub fn get_warnings(&self) -> &Vec<ParseWarning> {
         return &self.warnings;
     }
 
+    /// If warnings occurred, writes them one-by-one to the given writer, each on its own line, using the messages set with `set_messages()`.  
+    /// If there are no warnings, does nothing.
+    /// 
+    /// **Arguments**
+    ///  * `w`: The writer to write the warnings to.
+    /// 
+    /// **Returns**  
+    /// Nothing if the warnings were written successfully, or the error that occurred while writing otherwise.
+    pub fn write_warnings<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        // Simply write them all on the next line
+        for warning in self.warnings.iter() {
+            writeln!(w, "{}", self.messages.format_warning(warning))?;
+        }
+        return Ok(());
+    }
+
     /// If warnings occurred, prints them one-by-one to stderr.  
     /// If there are no warnings, does nothing.
     pub fn print_warnings(&self) {
         // Simply print them all on the next line
         for w in self.warnings.iter() {
-            eprintln!("{}", w);
+            eprintln!("{}", self.messages.format_warning(w));
         }
     }
 
@@ -1228,10 +7093,38 @@ impl ArgDict {
         self.options.contains_key(uid)
     }
 
+    /// Checks if an option with the given uid is given by the user with at least one value.
+    /// 
+    /// Useful for options with an optional value (e.g., `min_n_values = 0` and `max_n_values = 1`), where `has_opt()` is true even if no value is given.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to check.
+    /// 
+    /// **Returns**  
+    /// Whether or not the option is given with any values, as a boolean.
+    #[inline]
+    pub fn has_opt_with_value(&self, uid: &str) -> bool {
+        self.options.get(uid).map(|(_, _, values)| values.len() > 0).unwrap_or(false)
+    }
+
+    /// Returns where the values of the option with the given uid come from, e.g., to tell the user that an environment variable is used.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to check.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the source of the option's values or 'none' if the option has no values at all.
+    pub fn opt_source(&self, uid: &str) -> std::option::Option<Source> {
+        if !self.has_opt(uid) { return None; }
+        return Some(self.sources.get(uid).copied().unwrap_or(Source::CommandLine));
+    }
+
 
 
     /// Returns the value of the positional with the given uid.
     /// 
+    /// For variadic positionals, this returns the first of its values; use `get_pos_multi()` to get all of them.
+    /// 
     /// **Arguments**
     ///  * `uid`: The uid of the positional to get.
     /// 
@@ -1245,9 +7138,74 @@ impl ArgDict {
         }
     }
 
+    /// Returns the value of the positional with the given index, regardless of its uid.
+    /// 
+    /// **Arguments**
+    ///  * `index`: The index of the positional to get (as defined by the order in which the positionals were registered).
+    /// 
+    /// **Returns**  
+    /// An Option that is either the value of the positional or 'none' if no positional with that index was given.
+    pub fn get_pos_by_index(&self, index: usize) -> std::option::Option<&str> {
+        self.positionals.values().find(|(i, _)| *i == index).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all values of the variadic positional with the given uid.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the variadic positional to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the values of the positional as a list of Strings or 'none'.
+    pub fn get_pos_multi(&self, uid: &str) -> std::option::Option<&Vec<String>> {
+        match self.multi_positionals.get(uid) {
+            Some((_, values)) => Some(values),
+            None              => None,
+        }
+    }
+
+    /// Checks if nothing was given at all, i.e., no positionals, no options (including help) and no subcommand. Warnings and errors are ignored.
+    /// 
+    /// **Returns**  
+    /// `true` if nothing was given, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positionals.len() == 0 && self.options.len() == 0 && self.subcommand.is_none()
+    }
+
+    /// Checks if anything was given at all. The opposite of `is_empty()`.
+    /// 
+    /// **Returns**  
+    /// `true` if any positional, option or subcommand was given, or `false` otherwise.
+    #[inline]
+    pub fn has_any(&self) -> bool {
+        !self.is_empty()
+    }
+
+
+
+    /// Returns the number of positionals that were given a value.
+    /// 
+    /// **Returns**  
+    /// The number of filled positionals, which includes variadic ones.
+    #[inline]
+    pub fn num_pos(&self) -> usize {
+        self.positionals.len()
+    }
+
+    /// Returns the number of positional values that were skipped because more were given than there are positionals.
+    /// 
+    /// Each of these also results in a warning.
+    /// 
+    /// **Returns**  
+    /// The number of skipped positional values.
+    #[inline]
+    pub fn num_skipped_pos(&self) -> usize {
+        self.n_skipped_pos
+    }
+
     /// Returns the value(s) of the option with the given uid.
     /// 
-    /// If the Option has no value, returns an empty list.
+    /// If the Option has no value, returns an empty list. If the option is given multiple times, the values of all occurrences are collected in the order they appear on the command line, regardless of any other arguments in between. This is guaranteed, so e.g. '--header a --header b' results in both 'a' and 'b' (as long as the option accepts at least two values in total; repeating an option that accepts at most one value is an error, unless it's a map option or repeatable, see `ArgParser::set_repeatable()`).
     /// 
     /// **Arguments**
     ///  * `uid`: The uid of the option to get.
@@ -1262,4 +7220,308 @@ impl ArgDict {
         }
     }
 
+    /// Returns an iterator over the values of the option with the given uid, across all of its occurrences and in the order they were given (see `get_opt()`).
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An iterator that yields each value of the option. Yields nothing if the option wasn't given.
+    pub fn get_opt_each(&self, uid: &str) -> impl Iterator<Item = &str> {
+        self.get_opt(uid).into_iter().flat_map(|values| values.iter().map(|v| v.as_str()))
+    }
+
+    /// Returns the first value of the option with the given uid.
+    /// 
+    /// Useful for options that take exactly one value.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the first value of the option or 'none' if the option wasn't given or has no values.
+    pub fn get_opt_one(&self, uid: &str) -> std::option::Option<&str> {
+        match self.get_opt(uid) {
+            Some(values) if values.len() > 0 => Some(values[0].as_str()),
+            _                                => None,
+        }
+    }
+
+    /// Returns the first value of the option with the given uid, or the given default if it has none.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    ///  * `default`: The value to return if the option wasn't given or has no values.
+    /// 
+    /// **Returns**  
+    /// The first value of the option if it was given, or a copy of `default` otherwise.
+    #[inline]
+    pub fn get_opt_one_or(&self, uid: &str, default: &str) -> String {
+        String::from(self.get_opt_one(uid).unwrap_or(default))
+    }
+
+    /// Returns the values of the option with the given uid as a pair, e.g., for an option like `--point <x> <y>`.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the two values of the option or 'none' if the option wasn't given or doesn't have exactly two values.
+    pub fn get_opt_tuple2(&self, uid: &str) -> std::option::Option<(&str, &str)> {
+        match self.get_opt(uid) {
+            Some(values) if values.len() == 2 => Some((values[0].as_str(), values[1].as_str())),
+            _                                 => None,
+        }
+    }
+
+    /// Returns the values of the option with the given uid as a triple, e.g., for an option like `--color <r> <g> <b>`.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the three values of the option or 'none' if the option wasn't given or doesn't have exactly three values.
+    pub fn get_opt_tuple3(&self, uid: &str) -> std::option::Option<(&str, &str, &str)> {
+        match self.get_opt(uid) {
+            Some(values) if values.len() == 3 => Some((values[0].as_str(), values[1].as_str(), values[2].as_str())),
+            _                                 => None,
+        }
+    }
+
+
+
+    /// Returns an iterator over all positionals given by the user, in the order of their indices.
+    /// 
+    /// **Returns**  
+    /// An iterator that yields the uid and the value of each given positional.
+    pub fn iter_pos(&self) -> impl Iterator<Item = (&str, &str)> {
+        // Sort the positionals by index first
+        let mut positionals: Vec<(&String, &(usize, String))> = self.positionals.iter().collect();
+        positionals.sort_by_key(|(_, (index, _))| *index);
+        positionals.into_iter().map(|(uid, (_, value))| (uid.as_str(), value.as_str()))
+    }
+
+    /// Returns an iterator over all options given by the user, in no particular order.
+    /// 
+    /// **Returns**  
+    /// An iterator that yields the uid and the values of each given option.
+    pub fn iter_opts(&self) -> impl Iterator<Item = (&str, &Vec<String>)> {
+        self.options.iter().map(|(uid, (_, _, values))| (uid.as_str(), values))
+    }
+
+
+
+    /// Returns the state of the negatable flag with the given uid.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the flag to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is `true` if the flag was given, `false` if its negated form was given, or 'none' if neither was. If both are given, the last one counts.
+    #[inline]
+    pub fn get_flag(&self, uid: &str) -> std::option::Option<bool> {
+        self.flags.get(uid).map(|(_, state)| *state)
+    }
+
+
+
+    /// Returns how often the counted option with the given uid was given.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the counted option to get.
+    /// 
+    /// **Returns**  
+    /// The number of times the option was given, which is 0 if it wasn't given at all.
+    #[inline]
+    pub fn get_count(&self, uid: &str) -> usize {
+        self.counts.get(uid).copied().unwrap_or(0)
+    }
+
+    /// Returns the subcommand given by the user, if any.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the name of the subcommand and the dict with its parsed arguments, or 'none' if no subcommand was given.
+    pub fn get_subcommand(&self) -> std::option::Option<(&str, &ArgDict)> {
+        match &self.subcommand {
+            Some((name, dict)) => Some((name.as_str(), dict.as_ref())),
+            None               => None,
+        }
+    }
+
+    /// Returns the arguments given after the first positional, if the parser was told to stop there with `set_stop_at_first_positional()`.
+    /// 
+    /// **Returns**  
+    /// The list of trailing arguments, exactly as given. Is empty if there were none.
+    #[inline]
+    pub fn get_trailing(&self) -> &Vec<String> {
+        &self.trailing
+    }
+
+    /// Returns the unknown options given, if the parser was told to collect them with `set_collect_unknown()`.
+    /// 
+    /// **Returns**  
+    /// The list of unknown options, exactly as given (including any value given with an equals sign or attached to a shortname) and each followed by the values given separately from it. Is empty if there were none.
+    #[inline]
+    pub fn get_unknown(&self) -> &Vec<String> {
+        &self.unknown
+    }
+
+    /// Returns the index of the double dash that stopped the parsing of options, if the parser uses it (see `add_double_dash()`) and it was given.
+    /// 
+    /// This allows callers to distinguish between positionals given before and after it, e.g., to treat the latter as filenames only.
+    /// 
+    /// **Returns**  
+    /// The index of the double dash in the list of arguments (where the executable name has index 0), or None if it was not encountered.
+    #[inline]
+    pub fn double_dash_index(&self) -> std::option::Option<usize> {
+        self.double_dash
+    }
+
+
+
+    /// Returns the 'key=value' pairs given to the map option with the given uid as a map.
+    /// 
+    /// If a key is given multiple times, the last value counts.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the map option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the map of keys to values or 'none' if the option wasn't given.
+    pub fn get_opt_map(&self, uid: &str) -> std::option::Option<HashMap<String, String>> {
+        let values = self.get_opt(uid)?;
+
+        // Split each value on the first equals sign
+        let mut result: HashMap<String, String> = HashMap::with_capacity(values.len());
+        for value in values.iter() {
+            if let Some((key, value)) = value.split_once('=') {
+                result.insert(String::from(key), String::from(value));
+            }
+        }
+
+        // Done
+        return Some(result);
+    }
+
+
+
+    /// Returns the value of the positional with the given uid, parsed as the given type.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to parse the value as.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the positional to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the result of parsing the positional's value or 'none' if the positional wasn't given.
+    pub fn get_pos_as<T: FromStr>(&self, uid: &str) -> std::option::Option<Result<T, T::Err>> {
+        match self.get_pos(uid) {
+            Some(value) => Some(T::from_str(value)),
+            None        => None,
+        }
+    }
+
+    /// Returns the first value of the option with the given uid, parsed as the given type.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to parse the value as.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the result of parsing the option's first value or 'none' if the option wasn't given or has no values.
+    pub fn get_opt_as<T: FromStr>(&self, uid: &str) -> std::option::Option<Result<T, T::Err>> {
+        match self.get_opt(uid) {
+            Some(values) if values.len() > 0 => Some(T::from_str(&values[0])),
+            _                                => None,
+        }
+    }
+
+    /// Returns all values of the option with the given uid, each parsed as the given type.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to parse the values as.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the list of parse results (one per value, in order) or 'none' if the option wasn't given.
+    pub fn get_opt_all_as<T: FromStr>(&self, uid: &str) -> std::option::Option<Vec<Result<T, T::Err>>> {
+        match self.get_opt(uid) {
+            Some(values) => Some(values.iter().map(|v| T::from_str(v)).collect()),
+            None         => None,
+        }
+    }
+
+    /// Returns the value of the positional with the given uid, parsed as the given type, and fails if it wasn't given.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to parse the value as.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the positional to get.
+    /// 
+    /// **Returns**  
+    /// The parsed value on success, or a String describing why the positional is missing or couldn't be parsed otherwise.
+    pub fn require_pos_as<T: FromStr>(&self, uid: &str) -> Result<T, String> where T::Err: fmt::Display {
+        match self.get_pos_as::<T>(uid) {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(err))  => Err(format!("Could not parse value of positional '{}': {}", uid, err)),
+            None            => Err(format!("Missing positional '{}'.", uid)),
+        }
+    }
+
+    /// Returns the first value of the option with the given uid, parsed as the given type, and fails if it wasn't given or has no values.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to parse the value as.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// The parsed value on success, or a String describing why the option is missing or couldn't be parsed otherwise.
+    pub fn require_opt_as<T: FromStr>(&self, uid: &str) -> Result<T, String> where T::Err: fmt::Display {
+        match self.get_opt_as::<T>(uid) {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(err))  => Err(format!("Could not parse value of option '{}': {}", uid, err)),
+            None            => Err(format!("Missing value for option '{}'.", uid)),
+        }
+    }
+
+    /// Returns the first value of the option with the given uid as a boolean, e.g., for options registered with `ArgParser::add_opt_bool()`.
+    /// 
+    /// The values 'true', 'yes' and '1' are true, and 'false', 'no' and '0' are false, all case-insensitively.
+    /// 
+    /// **Arguments**
+    ///  * `uid`: The uid of the option to get.
+    /// 
+    /// **Returns**  
+    /// An Option that is either the boolean (or a String describing why the value isn't one) or 'none' if the option wasn't given or has no values.
+    pub fn get_bool(&self, uid: &str) -> std::option::Option<Result<bool, String>> {
+        let value = self.get_opt_one(uid)?;
+        match value.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Some(Ok(true)),
+            "false" | "no" | "0" => Some(Ok(false)),
+            _                    => Some(Err(format!("Invalid boolean '{}' for option '--{}' (expected true/false, yes/no or 1/0).", value, self.options.get(uid).unwrap().1))),
+        }
+    }
+
+
+
+    /// Constructs a type that implements FromArgDict from this dict.
+    /// 
+    /// **Template parameters**
+    ///  * `T`: The type to construct.
+    /// 
+    /// **Returns**  
+    /// The new instance on success, or a String describing what went wrong otherwise.
+    #[inline]
+    pub fn to_struct<T: FromArgDict>(&self) -> Result<T, String> {
+        T::from_dict(self)
+    }
+
 }